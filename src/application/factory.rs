@@ -0,0 +1,223 @@
+use crate::application::{TikalApp, TikalTransaction};
+use crate::domain::model::Entity;
+use crate::domain::value_objects::Value;
+use crate::domain::TikalResult;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+type Attributes = HashMap<String, Value>;
+type State = Arc<dyn Fn(usize) -> Attributes + Send + Sync>;
+
+pub struct Factory<E: Entity> {
+    definition: State,
+    states: Vec<State>,
+    count: usize,
+    // Shared by clones so every entity built from one factory gets its own number
+    sequence: Arc<AtomicUsize>,
+    parents: Vec<(String, Arc<dyn RelatedFactory>)>,
+    children: Vec<(String, Arc<dyn RelatedFactory>)>,
+    _entity: PhantomData<fn() -> E>,
+}
+
+impl<E: Entity + 'static> Factory<E> {
+    // The definition receives a sequence number, starting at 1, for unique values
+    pub fn new(definition: impl Fn(usize) -> Attributes + Send + Sync + 'static) -> Self {
+        Self {
+            definition: Arc::new(definition),
+            states: Vec::new(),
+            count: 1,
+            sequence: Arc::new(AtomicUsize::new(0)),
+            parents: Vec::new(),
+            children: Vec::new(),
+            _entity: PhantomData,
+        }
+    }
+
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn state(mut self, state: impl Fn(usize) -> Attributes + Send + Sync + 'static) -> Self {
+        self.states.push(Arc::new(state));
+        self
+    }
+
+    pub fn set(self, column: impl Into<String>, value: impl Into<Value>) -> Self {
+        let column = column.into();
+        let value = value.into();
+        self.state(move |_| HashMap::from([(column.clone(), value.clone())]))
+    }
+
+    // Cycles through the values, one per entity in the batch
+    pub fn sequence(self, column: impl Into<String>, values: Vec<Value>) -> Self {
+        if values.is_empty() {
+            return self;
+        }
+        let column = column.into();
+        let index = Arc::new(AtomicUsize::new(0));
+        self.state(move |_| {
+            let i = index.fetch_add(1, Ordering::SeqCst) % values.len();
+            HashMap::from([(column.clone(), values[i].clone())])
+        })
+    }
+
+    // Creates one parent and points every entity's foreign key at it
+    pub fn for_parent<P: Entity + 'static>(
+        mut self,
+        foreign_key: impl Into<String>,
+        parent: Factory<P>,
+    ) -> Self {
+        self.parents
+            .push((foreign_key.into(), Arc::new(parent.count(1))));
+        self
+    }
+
+    // Creates the child factory's entities for each entity, linked by foreign key
+    pub fn has<C: Entity + 'static>(
+        mut self,
+        children: Factory<C>,
+        foreign_key: impl Into<String>,
+    ) -> Self {
+        self.children.push((foreign_key.into(), Arc::new(children)));
+        self
+    }
+
+    pub fn make(&self) -> TikalResult<Vec<E>> {
+        self.attributes(&[]).into_iter().map(E::from_row).collect()
+    }
+
+    pub fn make_one(&self) -> TikalResult<E> {
+        E::from_row(self.next_attributes(&[]))
+    }
+
+    // Parents, entities and children are written in one transaction, so a
+    // failure part way through leaves nothing behind
+    pub async fn create(&self, app: &TikalApp) -> TikalResult<Vec<E>> {
+        app.transaction(|tx| async move { self.create_in(&tx).await })
+            .await
+    }
+
+    pub async fn create_one(&self, app: &TikalApp) -> TikalResult<E> {
+        app.transaction(|tx| async move { self.create_one_in(&tx).await })
+            .await
+    }
+
+    pub async fn create_in(&self, tx: &TikalTransaction) -> TikalResult<Vec<E>> {
+        Ok(self.persist(tx, &[]).await?.0)
+    }
+
+    pub async fn create_one_in(&self, tx: &TikalTransaction) -> TikalResult<E> {
+        let mut created = self.clone().count(1).persist(tx, &[]).await?.0;
+        Ok(created.remove(0))
+    }
+
+    async fn persist(
+        &self,
+        tx: &TikalTransaction,
+        fixed: &[(String, Value)],
+    ) -> TikalResult<(Vec<E>, Vec<Value>)> {
+        let mut fixed = fixed.to_vec();
+        for (foreign_key, parent) in &self.parents {
+            let key = parent
+                .create_related(tx, None)
+                .await?
+                .into_iter()
+                .next()
+                .unwrap_or(Value::Null);
+            fixed.push((foreign_key.clone(), key));
+        }
+
+        let mut rows = self.attributes(&fixed);
+        let entities = rows
+            .iter()
+            .map(|attributes| E::from_row(attributes.clone()))
+            .collect::<TikalResult<Vec<E>>>()?;
+        let keys = tx
+            .repository::<E>()
+            .save_many_returning_keys(&entities)
+            .await?;
+
+        // Rebuild the entities so they carry the keys the database generated
+        let pk = E::primary_key();
+        for (attributes, key) in rows.iter_mut().zip(&keys) {
+            attributes.insert(pk.to_string(), key.clone());
+        }
+        let entities = rows
+            .into_iter()
+            .map(E::from_row)
+            .collect::<TikalResult<Vec<E>>>()?;
+
+        for (foreign_key, children) in &self.children {
+            for key in &keys {
+                children
+                    .create_related(tx, Some((foreign_key.clone(), key.clone())))
+                    .await?;
+            }
+        }
+
+        Ok((entities, keys))
+    }
+
+    fn attributes(&self, fixed: &[(String, Value)]) -> Vec<Attributes> {
+        (0..self.count)
+            .map(|_| self.next_attributes(fixed))
+            .collect()
+    }
+
+    fn next_attributes(&self, fixed: &[(String, Value)]) -> Attributes {
+        let n = self.sequence.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut attributes = (self.definition)(n);
+        for state in &self.states {
+            attributes.extend(state(n));
+        }
+        attributes.extend(fixed.iter().cloned());
+
+        // Columns the definition leaves out are NULL, which lets the database
+        // generate the primary key (the field must then be an Option)
+        for column in E::table_definition().columns {
+            if column.nullable || column.primary_key {
+                attributes.entry(column.name).or_insert(Value::Null);
+            }
+        }
+        attributes
+    }
+}
+
+impl<E: Entity> Clone for Factory<E> {
+    fn clone(&self) -> Self {
+        Self {
+            definition: self.definition.clone(),
+            states: self.states.clone(),
+            count: self.count,
+            sequence: self.sequence.clone(),
+            parents: self.parents.clone(),
+            children: self.children.clone(),
+            _entity: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+trait RelatedFactory: Send + Sync {
+    async fn create_related(
+        &self,
+        tx: &TikalTransaction,
+        foreign_key: Option<(String, Value)>,
+    ) -> TikalResult<Vec<Value>>;
+}
+
+#[async_trait]
+impl<E: Entity + 'static> RelatedFactory for Factory<E> {
+    async fn create_related(
+        &self,
+        tx: &TikalTransaction,
+        foreign_key: Option<(String, Value)>,
+    ) -> TikalResult<Vec<Value>> {
+        let fixed: Vec<(String, Value)> = foreign_key.into_iter().collect();
+        Ok(self.persist(tx, &fixed).await?.1)
+    }
+}
@@ -1,5 +1,11 @@
 pub mod executor_utils;
+pub mod factory;
 pub mod orm;
+pub mod seeder;
+pub mod transaction;
 
 pub use executor_utils::*;
+pub use factory::Factory;
 pub use orm::*;
+pub use seeder::{Seeder, SeederRunner};
+pub use transaction::TikalTransaction;
@@ -1,10 +1,18 @@
+use crate::application::TikalTransaction;
 use crate::domain::model::Entity;
-use crate::domain::repositories::{executor::QueryExecutor, Repository};
+use crate::domain::repositories::{
+    executor::QueryExecutor, types::TransactionOptions, Repository,
+};
 use crate::domain::{query::builder::QueryBuilder, TikalResult};
 use crate::infrastructure::database::DatabasePool;
+use crate::infrastructure::drivers::TransactionExecutor;
 use crate::infrastructure::query_builder::generators::SqlGeneratorEnum;
 use crate::infrastructure::repositories::SqlRepository;
 use crate::infrastructure::schema::generators::{UnifiedDdlGenerator, DdlGenerator};
+use crate::infrastructure::schema::{
+    AlterTableDefinition, SchemaDefinition, SchemaDiff, SchemaDiffer, SchemaIntrospector,
+    TableDefinition,
+};
 
 #[cfg(feature = "mysql")]
 use crate::infrastructure::drivers::mysql::MySqlExecutor;
@@ -49,6 +57,11 @@ impl TikalApp {
         repo.save(entity).await
     }
 
+    pub async fn save_returning<E: Entity + 'static>(&self, entity: &E) -> TikalResult<E> {
+        let repo = self.repository::<E>();
+        repo.save_returning(entity).await
+    }
+
     pub async fn find_all<E: Entity + 'static>(&self) -> TikalResult<Vec<E>> {
         let repo = self.repository::<E>();
         repo.find_all().await
@@ -70,6 +83,16 @@ impl TikalApp {
         repo.find_with_query(query).await
     }
 
+    pub async fn upsert<E: Entity + 'static>(
+        &self,
+        entity: &E,
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> TikalResult<u64> {
+        let repo = self.repository::<E>();
+        repo.upsert(entity, conflict_columns, update_columns).await
+    }
+
     pub async fn update<E: Entity + 'static>(&self, entity: &E) -> TikalResult<u64> {
         let repo = self.repository::<E>();
         repo.update(entity).await
@@ -80,6 +103,23 @@ impl TikalApp {
         repo.delete(entity).await
     }
 
+    pub async fn update_where<E: Entity + 'static>(
+        &self,
+        query: QueryBuilder<E>,
+        assignments: Vec<crate::domain::query::assignment::Assignment>,
+    ) -> TikalResult<u64> {
+        let repo = self.repository::<E>();
+        repo.update_where(query, assignments).await
+    }
+
+    pub async fn delete_where<E: Entity + 'static>(
+        &self,
+        query: QueryBuilder<E>,
+    ) -> TikalResult<u64> {
+        let repo = self.repository::<E>();
+        repo.delete_where(query).await
+    }
+
     pub async fn count<E: Entity + 'static>(&self, query: QueryBuilder<E>) -> TikalResult<i64> {
         let repo = self.repository::<E>();
         repo.count(query).await
@@ -164,6 +204,95 @@ impl TikalApp {
         }
     }
 
+    pub async fn begin(&self) -> TikalResult<TikalTransaction> {
+        self.begin_with(TransactionOptions::default()).await
+    }
+
+    pub async fn begin_with(&self, options: TransactionOptions) -> TikalResult<TikalTransaction> {
+        let (executor, generator): (Box<dyn QueryExecutor>, SqlGeneratorEnum) = match &self.pool {
+            DatabasePool::MySql(pool) => (
+                Box::new(MySqlExecutor::new((**pool).clone())),
+                SqlGeneratorEnum::mysql(),
+            ),
+            DatabasePool::Postgres(pool) => (
+                Box::new(PostgresExecutor::new((**pool).clone())),
+                SqlGeneratorEnum::postgres(),
+            ),
+            DatabasePool::Sqlite(pool) => (
+                Box::new(SqliteExecutor::new((**pool).clone())),
+                SqlGeneratorEnum::sqlite(),
+            ),
+        };
+
+        let tx = executor.begin_transaction_with(options).await?;
+        let executor = TransactionExecutor::new(tx, executor.driver_info());
+        Ok(TikalTransaction::new(executor, generator))
+    }
+
+    pub async fn transaction<T, F, Fut>(&self, f: F) -> TikalResult<T>
+    where
+        F: FnMut(TikalTransaction) -> Fut,
+        Fut: std::future::Future<Output = TikalResult<T>>,
+    {
+        self.transaction_with_retry(0, f).await
+    }
+
+    pub async fn transaction_with_retry<T, F, Fut>(
+        &self,
+        max_retries: usize,
+        f: F,
+    ) -> TikalResult<T>
+    where
+        F: FnMut(TikalTransaction) -> Fut,
+        Fut: std::future::Future<Output = TikalResult<T>>,
+    {
+        self.transaction_with(TransactionOptions::default(), max_retries, f)
+            .await
+    }
+
+    pub async fn transaction_with<T, F, Fut>(
+        &self,
+        options: TransactionOptions,
+        max_retries: usize,
+        mut f: F,
+    ) -> TikalResult<T>
+    where
+        F: FnMut(TikalTransaction) -> Fut,
+        Fut: std::future::Future<Output = TikalResult<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let tx = self.begin_with(options.clone()).await?;
+            let result = match f(tx.clone()).await {
+                Ok(value) if tx.executor().is_active().await => tx.commit().await.map(|_| value),
+                Ok(value) => Ok(value),
+                Err(e) => {
+                    if tx.executor().is_active().await
+                        && let Err(rollback_err) = tx.rollback().await
+                    {
+                        tracing::warn!("Failed to roll back transaction: {}", rollback_err);
+                    }
+                    Err(e)
+                }
+            };
+
+            match result {
+                Err(e) if e.is_recoverable() && attempt < max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "Retrying transaction (attempt {} of {}): {}",
+                        attempt,
+                        max_retries,
+                        e
+                    );
+                    tokio::time::sleep(std::time::Duration::from_millis(100 * attempt as u64))
+                        .await;
+                }
+                result => return result,
+            }
+        }
+    }
+
     pub async fn ping(&self) -> TikalResult<bool> {
         match &self.pool {
             DatabasePool::MySql(pool) => {
@@ -190,11 +319,60 @@ impl TikalApp {
         }
     }
 
+    pub fn ddl_generator(&self) -> UnifiedDdlGenerator {
+        match &self.pool {
+            DatabasePool::MySql(_) => UnifiedDdlGenerator::mysql(),
+            DatabasePool::Postgres(_) => UnifiedDdlGenerator::postgres(),
+            DatabasePool::Sqlite(_) => UnifiedDdlGenerator::sqlite(),
+        }
+    }
+
+    pub async fn introspect_schema(&self) -> TikalResult<SchemaDefinition> {
+        let executor = self.executor();
+        SchemaIntrospector::new(executor.as_ref())
+            .introspect()
+            .await
+    }
+
+    pub async fn diff_schema(&self, tables: &[TableDefinition]) -> TikalResult<SchemaDiff> {
+        self.diff_schema_with(&SchemaDiffer::new(), tables).await
+    }
+
+    pub async fn diff_schema_with(
+        &self,
+        differ: &SchemaDiffer,
+        tables: &[TableDefinition],
+    ) -> TikalResult<SchemaDiff> {
+        let current = self.introspect_schema().await?;
+        Ok(differ.diff(&self.ddl_generator(), &current, tables))
+    }
+
+    pub async fn alter_table(&self, alter: &AlterTableDefinition) -> TikalResult<()> {
+        let executor = self.executor();
+        crate::infrastructure::schema::alter_table(executor.as_ref(), alter).await
+    }
+
+    fn executor(&self) -> Box<dyn QueryExecutor> {
+        match &self.pool {
+            DatabasePool::MySql(pool) => Box::new(MySqlExecutor::new((**pool).clone())),
+            DatabasePool::Postgres(pool) => Box::new(PostgresExecutor::new((**pool).clone())),
+            DatabasePool::Sqlite(pool) => Box::new(SqliteExecutor::new((**pool).clone())),
+        }
+    }
+
     pub async fn save_many<E: Entity + 'static>(&self, entities: &[E]) -> TikalResult<u64> {
         let repo = self.repository::<E>();
         repo.save_many(entities).await
     }
 
+    pub async fn save_many_returning_keys<E: Entity + 'static>(
+        &self,
+        entities: &[E],
+    ) -> TikalResult<Vec<crate::domain::value_objects::Value>> {
+        let repo = self.repository::<E>();
+        repo.save_many_returning_keys(entities).await
+    }
+
     pub async fn update_many<E: Entity + 'static>(&self, entities: &[E]) -> TikalResult<u64> {
         let repo = self.repository::<E>();
         repo.update_many(entities).await
@@ -0,0 +1,40 @@
+use crate::application::TikalApp;
+use crate::domain::TikalResult;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Seeder: Send + Sync {
+    async fn run(&self, app: &TikalApp) -> TikalResult<()>;
+
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+#[derive(Default)]
+pub struct SeederRunner {
+    seeders: Vec<Box<dyn Seeder>>,
+}
+
+impl SeederRunner {
+    pub fn new(seeders: Vec<Box<dyn Seeder>>) -> Self {
+        Self { seeders }
+    }
+
+    pub fn add_seeder(mut self, seeder: impl Seeder + 'static) -> Self {
+        self.seeders.push(Box::new(seeder));
+        self
+    }
+
+    // Seeders run in the order they were added and stop at the first failure
+    pub async fn run(&self, app: &TikalApp) -> TikalResult<()> {
+        for seeder in &self.seeders {
+            tracing::info!("Running seeder: {}", seeder.name());
+            seeder
+                .run(app)
+                .await
+                .map_err(|e| e.with_context(format!("Seeder '{}' failed", seeder.name())))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::domain::model::Entity;
+use crate::domain::repositories::{executor::QueryExecutor, Repository};
+use crate::domain::value_objects::Value;
+use crate::domain::TikalResult;
+use crate::infrastructure::drivers::TransactionExecutor;
+use crate::infrastructure::query_builder::generators::SqlGeneratorEnum;
+use crate::infrastructure::repositories::SqlRepository;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct TikalTransaction {
+    executor: TransactionExecutor,
+    generator: SqlGeneratorEnum,
+}
+
+impl TikalTransaction {
+    pub fn new(executor: TransactionExecutor, generator: SqlGeneratorEnum) -> Self {
+        Self {
+            executor,
+            generator,
+        }
+    }
+
+    pub fn repository<E: Entity + 'static>(&self) -> Box<dyn Repository<E>> {
+        Box::new(SqlRepository::new(
+            self.generator.clone(),
+            self.executor.clone(),
+        ))
+    }
+
+    pub fn executor(&self) -> &TransactionExecutor {
+        &self.executor
+    }
+
+    pub async fn execute_raw(&self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
+        self.executor.execute(sql, params).await
+    }
+
+    pub async fn query_raw(
+        &self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> TikalResult<Vec<HashMap<String, Value>>> {
+        self.executor.fetch_all(sql, params).await
+    }
+
+    pub async fn begin(&self) -> TikalResult<TikalTransaction> {
+        Ok(Self::new(
+            self.executor.begin().await?,
+            self.generator.clone(),
+        ))
+    }
+
+    pub async fn savepoint(&self, name: &str) -> TikalResult<()> {
+        self.executor.savepoint(name).await
+    }
+
+    pub async fn rollback_to(&self, name: &str) -> TikalResult<()> {
+        self.executor.rollback_to(name).await
+    }
+
+    pub async fn release(&self, name: &str) -> TikalResult<()> {
+        self.executor.release(name).await
+    }
+
+    pub async fn commit(self) -> TikalResult<()> {
+        self.executor.commit().await
+    }
+
+    pub async fn rollback(self) -> TikalResult<()> {
+        self.executor.rollback().await
+    }
+}
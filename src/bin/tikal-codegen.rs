@@ -0,0 +1,39 @@
+use tikal::infrastructure::schema::codegen::generate_entities;
+use tikal::infrastructure::schema::SchemaDefinition;
+
+const USAGE: &str = "Usage: tikal-codegen [-o <file>] [table ...]";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut output = None;
+    let mut tables = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = Some(args.next().ok_or(USAGE)?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => tables.push(arg),
+        }
+    }
+
+    let app = tikal::init().await?;
+    let schema = app.introspect_schema().await?;
+    let schema = SchemaDefinition {
+        tables: schema
+            .tables
+            .into_iter()
+            .filter(|t| !t.name.starts_with("__migrations"))
+            .filter(|t| tables.is_empty() || tables.contains(&t.name))
+            .collect(),
+    };
+
+    let code = generate_entities(&schema);
+    match output {
+        Some(path) => std::fs::write(path, code)?,
+        None => print!("{}", code),
+    }
+    Ok(())
+}
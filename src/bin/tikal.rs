@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use tikal::config::DatabaseConfig;
+use tikal::infrastructure::database::DatabaseFactory;
+use tikal::infrastructure::drivers::traits::QueryExecutor;
+use tikal::infrastructure::drivers::TransactionExecutor;
+use tikal::infrastructure::migrations::runner::MigrationRunner;
+use tikal::infrastructure::migrations::{sql, MigrationState};
+use tikal::infrastructure::schema::{
+    in_dependency_order, DdlGenerator, SchemaIntrospector, TableDefinition, UnifiedDdlGenerator,
+};
+
+const USAGE: &str = "Usage: tikal <command> [options]
+
+Commands:
+  migrate                        Apply pending migrations
+  migrate:rollback [--steps <n>] Roll back the latest migrations (default 1)
+  migrate:status                 Show the state of every migration
+  migrate:unlock                 Clear a SQLite migration lock left by a crashed run
+  make:migration <name>          Create an empty timestamped migration
+  schema:dump [-o <file>]        Print the current schema as SQL
+  db:seed                        Run the SQL files in the seeds directory
+
+Options:
+  --path <dir>                   Migrations directory (default: migrations)
+  --seeds <dir>                  Seeds directory (default: seeds)
+
+The connection is read from the DATABASE_* environment variables or .env.";
+
+struct Options {
+    command: String,
+    arguments: Vec<String>,
+    path: PathBuf,
+    seeds: PathBuf,
+    steps: usize,
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run().await {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<(), Box<dyn Error>> {
+    let Some(options) = parse_args()? else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+
+    // Scaffolding works without a database
+    if options.command == "make:migration" {
+        let [name] = options.arguments.as_slice() else {
+            return Err(USAGE.into());
+        };
+        let path = sql::write_migration(
+            &options.path,
+            name,
+            "-- Write the migration here\n",
+            Some("-- Write the rollback here\n"),
+        )?;
+        println!("Created {}", path.display());
+        return Ok(());
+    }
+
+    let config = DatabaseConfig::from_env()?;
+    let (executor, generator) = DatabaseFactory::create_database(&config).await?;
+    let executor = executor.as_ref();
+
+    match options.command.as_str() {
+        "migrate" => {
+            let migrations = sql::load_migrations(&options.path)?;
+            MigrationRunner::new(executor, &generator, migrations)
+                .run_pending_migrations()
+                .await?;
+            println!("Migrations are up to date");
+        }
+        "migrate:rollback" => {
+            let migrations = sql::load_migrations(&options.path)?;
+            let reverted = MigrationRunner::new(executor, &generator, migrations)
+                .rollback(options.steps)
+                .await?;
+            for record in &reverted {
+                println!("Rolled back {} ({})", record.name, record.version);
+            }
+            println!("Rolled back {} migration(s)", reverted.len());
+        }
+        "migrate:unlock" => {
+            MigrationRunner::new(executor, &generator, vec![])
+                .force_unlock()
+                .await?;
+            println!("Migration lock released");
+        }
+        "migrate:status" => {
+            let migrations = sql::load_migrations(&options.path)?;
+            let statuses = MigrationRunner::new(executor, &generator, migrations)
+                .status()
+                .await?;
+            for status in statuses {
+                let state = match status.state {
+                    MigrationState::Applied => "Applied",
+                    MigrationState::Pending => "Pending",
+                    MigrationState::Missing => "Missing",
+                    MigrationState::Modified => "Modified",
+                };
+                let applied_at = status
+                    .applied_at
+                    .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_default();
+                println!(
+                    "{:<9} {:<16} {:<40} {}",
+                    state, status.version, status.name, applied_at
+                );
+            }
+        }
+        "schema:dump" => {
+            let dump = dump_schema(executor).await?;
+            match &options.output {
+                Some(path) => std::fs::write(path, dump)?,
+                None => print!("{}", dump),
+            }
+        }
+        "db:seed" => seed(executor, &options.seeds).await?,
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+fn parse_args() -> Result<Option<Options>, Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let mut options = Options {
+        command: String::new(),
+        arguments: Vec::new(),
+        path: PathBuf::from("migrations"),
+        seeds: PathBuf::from("seeds"),
+        steps: 1,
+        output: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--path" => options.path = args.next().ok_or(USAGE)?.into(),
+            "--seeds" => options.seeds = args.next().ok_or(USAGE)?.into(),
+            "--steps" => {
+                options.steps = args
+                    .next()
+                    .and_then(|steps| steps.parse().ok())
+                    .ok_or("--steps expects a number")?
+            }
+            "-o" | "--output" => options.output = Some(args.next().ok_or(USAGE)?.into()),
+            _ if options.command.is_empty() => options.command = arg,
+            _ => options.arguments.push(arg),
+        }
+    }
+
+    if options.command.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(options))
+}
+
+async fn dump_schema(executor: &dyn QueryExecutor) -> Result<String, Box<dyn Error>> {
+    let ddl = UnifiedDdlGenerator::for_driver(&executor.driver_info().driver_type);
+    let schema = SchemaIntrospector::new(executor).introspect().await?;
+    let tables: Vec<TableDefinition> = schema
+        .tables
+        .into_iter()
+        .filter(|t| !t.name.starts_with("__migrations"))
+        .collect();
+
+    let mut dump = String::new();
+    let columns: Vec<_> = tables.iter().flat_map(|t| t.columns.clone()).collect();
+    let types = ddl.generate_create_types(&columns);
+    for statement in &types {
+        push_statement(&mut dump, statement);
+    }
+    if !types.is_empty() {
+        dump.push('\n');
+    }
+    for table in in_dependency_order(tables) {
+        push_statement(&mut dump, &ddl.generate_create_table(&table));
+        for index in &table.indexes {
+            push_statement(
+                &mut dump,
+                &ddl.generate_create_index(&table.name, &index.name, &index.columns, index.unique),
+            );
+        }
+        dump.push('\n');
+    }
+    Ok(dump)
+}
+
+fn push_statement(dump: &mut String, statement: &str) {
+    dump.push_str(statement.trim_end().trim_end_matches(';'));
+    dump.push_str(";\n");
+}
+
+async fn seed(executor: &dyn QueryExecutor, dir: &Path) -> Result<(), Box<dyn Error>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Cannot read seeds directory '{}': {}", dir.display(), e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    files.sort();
+
+    for file in files {
+        let statements = sql::split_statements(
+            &std::fs::read_to_string(&file)?,
+            &executor.driver_info().driver_type,
+        );
+        let tx = executor.begin_transaction().await?;
+        let tx = TransactionExecutor::new(tx, executor.driver_info());
+        for statement in &statements {
+            if let Err(e) = tx.execute(statement, vec![]).await {
+                tx.rollback().await?;
+                return Err(e
+                    .with_context(format!("Seed '{}' failed", file.display()))
+                    .into());
+            }
+        }
+        tx.commit().await?;
+        println!("Seeded {}", file.display());
+    }
+    Ok(())
+}
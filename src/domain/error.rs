@@ -7,27 +7,27 @@ pub enum TikalError {
     InvalidState {
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
     #[error("Configuration error: {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
     Configuration {
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Feature not implemented: {feature}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
     NotImplemented {
         feature: &'static str,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Internal error: {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
     InternalError {
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Database error: {db_message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -35,7 +35,7 @@ pub enum TikalError {
         db_message: String,
         context: Option<String>,
         error_code: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Validation error on '{field}': {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -43,7 +43,7 @@ pub enum TikalError {
         field: String,
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Connection error ({driver}): {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -52,7 +52,7 @@ pub enum TikalError {
         message: String,
         context: Option<String>,
         retry_count: Option<u32>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Query error: {message} (SQL: {sql}){}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -61,7 +61,7 @@ pub enum TikalError {
         message: String,
         context: Option<String>,
         params_count: Option<usize>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Mapping error ({entity}): {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -69,7 +69,7 @@ pub enum TikalError {
         entity: String,
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Transaction error ({transaction_id}): {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -77,7 +77,7 @@ pub enum TikalError {
         transaction_id: String,
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Record not found: {entity} with ID {id}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -85,7 +85,7 @@ pub enum TikalError {
         entity: String,
         id: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Unique constraint violation ({constraint}): {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -94,7 +94,7 @@ pub enum TikalError {
         message: String,
         context: Option<String>,
         conflicting_value: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Foreign key violation ({constraint}): {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -103,14 +103,14 @@ pub enum TikalError {
         message: String,
         context: Option<String>,
         referenced_table: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Infrastructure error: {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
     Infrastructure {
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("SQL injection attempt: {reason} (input: {input}){}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -118,7 +118,7 @@ pub enum TikalError {
         input: String,
         reason: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Connection timeout ({driver}) after {duration_ms}ms{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -126,7 +126,7 @@ pub enum TikalError {
         driver: String,
         duration_ms: u64,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Query timeout after {duration_ms}ms: {sql}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -134,7 +134,7 @@ pub enum TikalError {
         sql: String,
         duration_ms: u64,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Migration lock failed for '{migration}'{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -142,7 +142,7 @@ pub enum TikalError {
         migration: String,
         holder: Option<String>,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("NULL constraint violation: column '{column}' in table '{table}' cannot be NULL{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
@@ -150,14 +150,14 @@ pub enum TikalError {
         column: String,
         table: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 
     #[error("Error: {message}{}", .context.as_ref().map(|c| format!(" (context: {c})")).unwrap_or_default())]
     GenericError {
         message: String,
         context: Option<String>,
-        backtrace: Option<Backtrace>,
+        backtrace: Option<Box<Backtrace>>,
     },
 }
 
@@ -172,7 +172,7 @@ impl From<sqlx::Error> for TikalError {
             db_message: err.to_string(),
             context: None,
             error_code,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 }
@@ -181,7 +181,7 @@ impl TikalError {
         TikalError::InvalidState {
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -189,7 +189,7 @@ impl TikalError {
         TikalError::Configuration {
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -198,7 +198,7 @@ impl TikalError {
             field: field.to_string(),
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -206,7 +206,7 @@ impl TikalError {
         TikalError::InternalError {
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -215,7 +215,7 @@ impl TikalError {
             db_message: message.to_string(),
             context: None,
             error_code: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -224,7 +224,7 @@ impl TikalError {
             db_message: message.to_string(),
             context: Some(context.to_string()),
             error_code,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -232,7 +232,7 @@ impl TikalError {
         TikalError::InternalError {
             message: message.to_string(),
             context,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -242,7 +242,7 @@ impl TikalError {
             message: msg.to_string(),
             context: None,
             retry_count: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -252,7 +252,7 @@ impl TikalError {
             message: msg.to_string(),
             context: None,
             params_count: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -261,7 +261,7 @@ impl TikalError {
             entity: entity.to_string(),
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -270,7 +270,7 @@ impl TikalError {
             transaction_id: tx_id.to_string(),
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -278,7 +278,7 @@ impl TikalError {
         TikalError::NotImplemented {
             feature,
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -287,7 +287,7 @@ impl TikalError {
             entity: entity.to_string(),
             id: id.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -297,7 +297,7 @@ impl TikalError {
             message: msg.to_string(),
             context: None,
             conflicting_value: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -307,7 +307,7 @@ impl TikalError {
             message: msg.to_string(),
             context: None,
             referenced_table: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -316,7 +316,16 @@ impl TikalError {
             column: column.to_string(),
             table: table.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
+        }
+    }
+
+    pub fn migration_lock_failed(migration: &str, holder: Option<String>) -> Self {
+        TikalError::MigrationLockFailed {
+            migration: migration.to_string(),
+            holder,
+            context: None,
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -324,7 +333,7 @@ impl TikalError {
         TikalError::Infrastructure {
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -333,7 +342,7 @@ impl TikalError {
             input: input.to_string(),
             reason: reason.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -341,7 +350,7 @@ impl TikalError {
         TikalError::GenericError {
             message: msg.to_string(),
             context: None,
-            backtrace: Some(Backtrace::capture()),
+            backtrace: Some(Box::new(Backtrace::capture())),
         }
     }
 
@@ -380,7 +389,17 @@ impl TikalError {
             TikalError::ConnectionTimeout { .. }
                 | TikalError::QueryTimeout { .. }
                 | TikalError::ConnectionError { .. }
-        )
+        ) || self.is_serialization_failure()
+    }
+
+    pub fn is_serialization_failure(&self) -> bool {
+        match self {
+            TikalError::DatabaseError {
+                error_code: Some(code),
+                ..
+            } => matches!(code.as_str(), "40001" | "40P01" | "5" | "6" | "261" | "517"),
+            _ => false,
+        }
     }
 
     pub fn is_user_error(&self) -> bool {
@@ -415,7 +434,7 @@ impl TikalError {
             | TikalError::QueryTimeout { backtrace, .. }
             | TikalError::MigrationLockFailed { backtrace, .. }
             | TikalError::NullConstraintViolation { backtrace, .. }
-            | TikalError::GenericError { backtrace, .. } => backtrace.as_ref(),
+            | TikalError::GenericError { backtrace, .. } => backtrace.as_deref(),
         }
     }
 }
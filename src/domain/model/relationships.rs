@@ -1,3 +1,4 @@
+use crate::domain::query::builder::{JoinClause, JoinType, Operator};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -88,6 +89,43 @@ impl RelationshipMeta {
         self.cascade_delete = true;
         self
     }
+
+    pub fn join_clauses(&self, base_table: &str, join_type: JoinType) -> Vec<JoinClause> {
+        let target = &self.target_table;
+        match self.rel_type {
+            RelationshipType::BelongsTo => vec![JoinClause::new(target, join_type).on(
+                &format!("{}.{}", base_table, self.foreign_key),
+                Operator::Eq,
+                &format!("{}.id", target),
+            )],
+            RelationshipType::HasMany | RelationshipType::HasOne => {
+                vec![JoinClause::new(target, join_type).on(
+                    &format!("{}.{}", target, self.foreign_key),
+                    Operator::Eq,
+                    &format!("{}.id", base_table),
+                )]
+            }
+            RelationshipType::ManyToMany => {
+                let (Some(join_table), Some(target_fk)) =
+                    (&self.join_table, &self.target_foreign_key)
+                else {
+                    return Vec::new();
+                };
+                vec![
+                    JoinClause::new(join_table, join_type).on(
+                        &format!("{}.{}", join_table, self.foreign_key),
+                        Operator::Eq,
+                        &format!("{}.id", base_table),
+                    ),
+                    JoinClause::new(target, join_type).on(
+                        &format!("{}.id", target),
+                        Operator::Eq,
+                        &format!("{}.{}", join_table, target_fk),
+                    ),
+                ]
+            }
+        }
+    }
 }
 
 pub type RelationshipMap = HashMap<String, RelationshipMeta>;
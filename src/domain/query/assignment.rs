@@ -0,0 +1,59 @@
+use crate::domain::value_objects::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssignmentValue {
+    Value(Value),
+    Column(String),
+    Increment(Value),
+    Decrement(Value),
+    Raw(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub column: String,
+    pub value: AssignmentValue,
+}
+
+impl Assignment {
+    pub fn new(column: String, value: AssignmentValue) -> Self {
+        Self { column, value }
+    }
+
+    pub fn set(column: &str, value: impl Into<Value>) -> Self {
+        Self::new(column.to_string(), AssignmentValue::Value(value.into()))
+    }
+
+    pub fn set_null(column: &str) -> Self {
+        Self::new(column.to_string(), AssignmentValue::Value(Value::Null))
+    }
+
+    pub fn set_column(column: &str, source: &str) -> Self {
+        Self::new(
+            column.to_string(),
+            AssignmentValue::Column(source.to_string()),
+        )
+    }
+
+    pub fn increment(column: &str, amount: impl Into<Value>) -> Self {
+        Self::new(
+            column.to_string(),
+            AssignmentValue::Increment(amount.into()),
+        )
+    }
+
+    pub fn decrement(column: &str, amount: impl Into<Value>) -> Self {
+        Self::new(
+            column.to_string(),
+            AssignmentValue::Decrement(amount.into()),
+        )
+    }
+
+    pub fn current_timestamp(column: &str) -> Self {
+        Self::raw(column, "CURRENT_TIMESTAMP")
+    }
+
+    pub fn raw(column: &str, sql: &str) -> Self {
+        Self::new(column.to_string(), AssignmentValue::Raw(sql.to_string()))
+    }
+}
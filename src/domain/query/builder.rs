@@ -1,5 +1,7 @@
 use crate::domain::model::{Entity, ModelMapping};
+use crate::domain::query::filter::{Filter, FilterLogic};
 use crate::domain::value_objects::Value;
+use crate::infrastructure::schema::types::TableDefinition;
 use std::marker::PhantomData;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -11,7 +13,23 @@ pub enum Operator {
     Gte,
     Lte,
     Like,
+    NotLike,
+    ILike,
     In,
+    NotIn,
+    Between,
+    IsNull,
+    IsNotNull,
+    // The array column holds every value of the operand array
+    Contains,
+    // The operand is one of the array column's values
+    Any,
+}
+
+impl Operator {
+    pub fn is_unary(&self) -> bool {
+        matches!(self, Operator::IsNull | Operator::IsNotNull)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -33,17 +51,175 @@ pub struct Condition {
     pub values: Vec<Value>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ColumnCondition {
+    pub left: String,
+    pub operator: Operator,
+    pub right: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubqueryCondition {
+    pub column: String,
+    pub operator: Operator,
+    pub query: Box<Subquery>,
+}
+
+#[derive(Debug, Clone)]
+pub enum WhereNode {
+    Condition(Condition),
+    Column(ColumnCondition),
+    Subquery(SubqueryCondition),
+    Exists(Box<Subquery>),
+    Group(Vec<WhereClause>),
+}
+
+#[derive(Debug, Clone)]
+pub struct WhereClause {
+    pub logic: FilterLogic,
+    pub negated: bool,
+    pub node: WhereNode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+}
+
+#[derive(Debug, Clone)]
+pub struct JoinClause {
+    pub table: String,
+    pub alias: Option<String>,
+    pub join_type: JoinType,
+    pub on: Vec<WhereClause>,
+}
+
+impl JoinClause {
+    pub fn new(table: &str, join_type: JoinType) -> Self {
+        Self {
+            table: table.to_string(),
+            alias: None,
+            join_type,
+            on: Vec::new(),
+        }
+    }
+
+    pub fn inner(table: &str) -> Self {
+        Self::new(table, JoinType::Inner)
+    }
+
+    pub fn left(table: &str) -> Self {
+        Self::new(table, JoinType::Left)
+    }
+
+    pub fn right(table: &str) -> Self {
+        Self::new(table, JoinType::Right)
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.alias = Some(alias.to_string());
+        self
+    }
+
+    pub fn on(self, left: &str, operator: Operator, right: &str) -> Self {
+        self.push_on(FilterLogic::And, column_node(left, operator, right))
+    }
+
+    pub fn or_on(self, left: &str, operator: Operator, right: &str) -> Self {
+        self.push_on(FilterLogic::Or, column_node(left, operator, right))
+    }
+
+    pub fn on_value(self, column: &str, operator: Operator, value: impl Into<Value>) -> Self {
+        self.push_on(
+            FilterLogic::And,
+            WhereNode::Condition(Condition {
+                column: column.to_string(),
+                operator,
+                values: vec![value.into()],
+            }),
+        )
+    }
+
+    fn push_on(mut self, logic: FilterLogic, node: WhereNode) -> Self {
+        self.on.push(WhereClause {
+            logic,
+            negated: false,
+            node,
+        });
+        self
+    }
+}
+
+fn column_node(left: &str, operator: Operator, right: &str) -> WhereNode {
+    WhereNode::Column(ColumnCondition {
+        left: left.to_string(),
+        operator,
+        right: right.to_string(),
+    })
+}
+
+fn array(values: Vec<impl Into<Value>>) -> Value {
+    Value::Array(values.into_iter().map(|v| v.into()).collect())
+}
+
+// A query on another entity, kept as builder state until the query embedding
+// it is lowered
+#[derive(Debug, Clone)]
+pub struct Subquery {
+    pub table_name: String,
+    pub alias: Option<String>,
+    pub from_query: Option<Box<Subquery>>,
+    pub selected_columns: Vec<String>,
+    pub distinct: bool,
+    pub joins: Vec<JoinClause>,
+    pub filters: Vec<WhereClause>,
+    pub group_by: Vec<String>,
+    pub having_filters: Vec<Condition>,
+    pub order_by: Vec<OrderBy>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub table_definition: fn() -> TableDefinition,
+}
+
+impl<E: Entity> From<&QueryBuilder<E>> for Subquery {
+    fn from(query: &QueryBuilder<E>) -> Self {
+        Self {
+            table_name: query.table_name.clone(),
+            alias: query.alias.clone(),
+            from_query: query.from_query.clone(),
+            selected_columns: query.selected_columns.clone(),
+            distinct: query.distinct,
+            joins: query.joins.clone(),
+            filters: query.filters.clone(),
+            group_by: query.group_by.clone(),
+            having_filters: query.having_filters.clone(),
+            order_by: query.order_by.clone(),
+            limit: query.limit,
+            offset: query.offset,
+            table_definition: E::table_definition,
+        }
+    }
+}
+
 pub struct QueryBuilder<E: Entity> {
     pub table_name: String,
+    pub alias: Option<String>,
+    pub from_query: Option<Box<Subquery>>,
     pub selected_columns: Vec<String>,
     pub distinct: bool,
-    pub filters: Vec<Condition>,
+    pub joins: Vec<JoinClause>,
+    pub filters: Vec<WhereClause>,
     pub group_by: Vec<String>,
     pub having_filters: Vec<Condition>,
     pub order_by: Vec<OrderBy>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
     pub with_relations: Vec<String>,
+    // Lets update_where and delete_where run without filters
+    pub unfiltered: bool,
     _entity: PhantomData<E>,
 }
 
@@ -51,8 +227,11 @@ impl<E: Entity> QueryBuilder<E> {
     pub fn new() -> Self {
         Self {
             table_name: E::table_name().to_string(),
+            alias: None,
+            from_query: None,
             selected_columns: Vec::new(),
             distinct: false,
+            joins: Vec::new(),
             filters: Vec::new(),
             group_by: Vec::new(),
             having_filters: Vec::new(),
@@ -60,56 +239,397 @@ impl<E: Entity> QueryBuilder<E> {
             limit: None,
             offset: None,
             with_relations: Vec::new(),
+            unfiltered: false,
             _entity: PhantomData,
         }
     }
 
-    pub fn where_clause(
-        mut self,
-        column: &str,
+    fn push_filter(mut self, logic: FilterLogic, negated: bool, node: WhereNode) -> Self {
+        self.filters.push(WhereClause {
+            logic,
+            negated,
+            node,
+        });
+        self
+    }
+
+    fn push_condition(
+        self,
+        logic: FilterLogic,
+        column: String,
         operator: Operator,
-        value: impl Into<Value>,
+        values: Vec<Value>,
     ) -> Self {
-        self.filters.push(Condition {
-            column: column.to_string(),
+        self.push_filter(
+            logic,
+            false,
+            WhereNode::Condition(Condition {
+                column,
+                operator,
+                values,
+            }),
+        )
+    }
+
+    fn push_group<F>(self, logic: FilterLogic, negated: bool, group_fn: F) -> Self
+    where
+        F: FnOnce(QueryBuilder<E>) -> QueryBuilder<E>,
+    {
+        let group = group_fn(QueryBuilder::new());
+        if group.filters.is_empty() {
+            return self;
+        }
+        self.push_filter(logic, negated, WhereNode::Group(group.filters))
+    }
+
+    pub fn where_clause(self, column: &str, operator: Operator, value: impl Into<Value>) -> Self {
+        self.push_condition(
+            FilterLogic::And,
+            column.to_string(),
             operator,
-            values: vec![value.into()],
-        });
-        self
+            vec![value.into()],
+        )
     }
 
-    pub fn where_in(mut self, column: &str, values: Vec<impl Into<Value>>) -> Self {
-        self.filters.push(Condition {
-            column: column.to_string(),
-            operator: Operator::In,
-            values: values.into_iter().map(|v| v.into()).collect(),
-        });
-        self
+    pub fn or_where(self, column: &str, operator: Operator, value: impl Into<Value>) -> Self {
+        self.push_condition(
+            FilterLogic::Or,
+            column.to_string(),
+            operator,
+            vec![value.into()],
+        )
+    }
+
+    pub fn where_in(self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        self.push_condition(
+            FilterLogic::And,
+            column.to_string(),
+            Operator::In,
+            values.into_iter().map(|v| v.into()).collect(),
+        )
+    }
+
+    pub fn or_where_in(self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        self.push_condition(
+            FilterLogic::Or,
+            column.to_string(),
+            Operator::In,
+            values.into_iter().map(|v| v.into()).collect(),
+        )
+    }
+
+    pub fn where_not_in(self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        self.push_condition(
+            FilterLogic::And,
+            column.to_string(),
+            Operator::NotIn,
+            values.into_iter().map(|v| v.into()).collect(),
+        )
+    }
+
+    pub fn or_where_not_in(self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        self.push_condition(
+            FilterLogic::Or,
+            column.to_string(),
+            Operator::NotIn,
+            values.into_iter().map(|v| v.into()).collect(),
+        )
+    }
+
+    pub fn where_null(self, column: &str) -> Self {
+        self.push_condition(
+            FilterLogic::And,
+            column.to_string(),
+            Operator::IsNull,
+            Vec::new(),
+        )
+    }
+
+    pub fn or_where_null(self, column: &str) -> Self {
+        self.push_condition(
+            FilterLogic::Or,
+            column.to_string(),
+            Operator::IsNull,
+            Vec::new(),
+        )
+    }
+
+    pub fn where_not_null(self, column: &str) -> Self {
+        self.push_condition(
+            FilterLogic::And,
+            column.to_string(),
+            Operator::IsNotNull,
+            Vec::new(),
+        )
+    }
+
+    pub fn or_where_not_null(self, column: &str) -> Self {
+        self.push_condition(
+            FilterLogic::Or,
+            column.to_string(),
+            Operator::IsNotNull,
+            Vec::new(),
+        )
+    }
+
+    pub fn where_between(
+        self,
+        column: &str,
+        low: impl Into<Value>,
+        high: impl Into<Value>,
+    ) -> Self {
+        self.push_condition(
+            FilterLogic::And,
+            column.to_string(),
+            Operator::Between,
+            vec![low.into(), high.into()],
+        )
+    }
+
+    pub fn or_where_between(
+        self,
+        column: &str,
+        low: impl Into<Value>,
+        high: impl Into<Value>,
+    ) -> Self {
+        self.push_condition(
+            FilterLogic::Or,
+            column.to_string(),
+            Operator::Between,
+            vec![low.into(), high.into()],
+        )
+    }
+
+    pub fn where_like(self, column: &str, pattern: impl Into<Value>) -> Self {
+        self.where_clause(column, Operator::Like, pattern)
     }
 
-    pub fn where_field(mut self, field: &str, operator: Operator, value: impl Into<Value>) -> Self
+    pub fn where_not_like(self, column: &str, pattern: impl Into<Value>) -> Self {
+        self.where_clause(column, Operator::NotLike, pattern)
+    }
+
+    pub fn or_where_like(self, column: &str, pattern: impl Into<Value>) -> Self {
+        self.or_where(column, Operator::Like, pattern)
+    }
+
+    pub fn or_where_not_like(self, column: &str, pattern: impl Into<Value>) -> Self {
+        self.or_where(column, Operator::NotLike, pattern)
+    }
+
+    pub fn where_ilike(self, column: &str, pattern: impl Into<Value>) -> Self {
+        self.where_clause(column, Operator::ILike, pattern)
+    }
+
+    pub fn or_where_ilike(self, column: &str, pattern: impl Into<Value>) -> Self {
+        self.or_where(column, Operator::ILike, pattern)
+    }
+
+    pub fn where_contains(self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        self.where_clause(column, Operator::Contains, array(values))
+    }
+
+    pub fn or_where_contains(self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        self.or_where(column, Operator::Contains, array(values))
+    }
+
+    pub fn where_any(self, column: &str, value: impl Into<Value>) -> Self {
+        self.where_clause(column, Operator::Any, value)
+    }
+
+    pub fn or_where_any(self, column: &str, value: impl Into<Value>) -> Self {
+        self.or_where(column, Operator::Any, value)
+    }
+
+    pub fn where_column(self, left: &str, operator: Operator, right: &str) -> Self {
+        self.push_filter(FilterLogic::And, false, column_node(left, operator, right))
+    }
+
+    pub fn or_where_column(self, left: &str, operator: Operator, right: &str) -> Self {
+        self.push_filter(FilterLogic::Or, false, column_node(left, operator, right))
+    }
+
+    pub fn where_subquery<S: Entity>(
+        self,
+        column: &str,
+        operator: Operator,
+        query: QueryBuilder<S>,
+    ) -> Self {
+        self.push_subquery(FilterLogic::And, column, operator, query)
+    }
+
+    pub fn where_in_subquery<S: Entity>(self, column: &str, query: QueryBuilder<S>) -> Self {
+        self.push_subquery(FilterLogic::And, column, Operator::In, query)
+    }
+
+    pub fn or_where_in_subquery<S: Entity>(self, column: &str, query: QueryBuilder<S>) -> Self {
+        self.push_subquery(FilterLogic::Or, column, Operator::In, query)
+    }
+
+    pub fn where_not_in_subquery<S: Entity>(self, column: &str, query: QueryBuilder<S>) -> Self {
+        self.push_subquery(FilterLogic::And, column, Operator::NotIn, query)
+    }
+
+    pub fn where_exists<S: Entity>(self, query: QueryBuilder<S>) -> Self {
+        self.push_exists(FilterLogic::And, false, query)
+    }
+
+    pub fn or_where_exists<S: Entity>(self, query: QueryBuilder<S>) -> Self {
+        self.push_exists(FilterLogic::Or, false, query)
+    }
+
+    pub fn where_not_exists<S: Entity>(self, query: QueryBuilder<S>) -> Self {
+        self.push_exists(FilterLogic::And, true, query)
+    }
+
+    pub fn or_where_not_exists<S: Entity>(self, query: QueryBuilder<S>) -> Self {
+        self.push_exists(FilterLogic::Or, true, query)
+    }
+
+    fn push_subquery<S: Entity>(
+        self,
+        logic: FilterLogic,
+        column: &str,
+        operator: Operator,
+        query: QueryBuilder<S>,
+    ) -> Self {
+        self.push_filter(
+            logic,
+            false,
+            WhereNode::Subquery(SubqueryCondition {
+                column: column.to_string(),
+                operator,
+                query: Box::new(Subquery::from(&query)),
+            }),
+        )
+    }
+
+    fn push_exists<S: Entity>(
+        self,
+        logic: FilterLogic,
+        negated: bool,
+        query: QueryBuilder<S>,
+    ) -> Self {
+        let query = Box::new(Subquery::from(&query));
+        self.push_filter(logic, negated, WhereNode::Exists(query))
+    }
+
+    pub fn where_field(self, field: &str, operator: Operator, value: impl Into<Value>) -> Self
     where
         E: ModelMapping,
     {
         let column = E::field_to_column(field).unwrap_or_else(|| field.to_string());
-        self.filters.push(Condition {
-            column,
-            operator,
-            values: vec![value.into()],
-        });
-        self
+        self.push_condition(FilterLogic::And, column, operator, vec![value.into()])
     }
 
-    pub fn where_field_in(mut self, field: &str, values: Vec<impl Into<Value>>) -> Self
+    pub fn where_field_in(self, field: &str, values: Vec<impl Into<Value>>) -> Self
     where
         E: ModelMapping,
     {
         let column = E::field_to_column(field).unwrap_or_else(|| field.to_string());
-        self.filters.push(Condition {
+        self.push_condition(
+            FilterLogic::And,
             column,
-            operator: Operator::In,
-            values: values.into_iter().map(|v| v.into()).collect(),
-        });
+            Operator::In,
+            values.into_iter().map(|v| v.into()).collect(),
+        )
+    }
+
+    pub fn where_filter(self, filter: Filter) -> Self {
+        self.push_condition(
+            FilterLogic::And,
+            filter.column,
+            filter.operator,
+            filter.values,
+        )
+    }
+
+    pub fn or_where_filter(self, filter: Filter) -> Self {
+        self.push_condition(
+            FilterLogic::Or,
+            filter.column,
+            filter.operator,
+            filter.values,
+        )
+    }
+
+    pub fn where_group<F>(self, group_fn: F) -> Self
+    where
+        F: FnOnce(QueryBuilder<E>) -> QueryBuilder<E>,
+    {
+        self.push_group(FilterLogic::And, false, group_fn)
+    }
+
+    pub fn or_where_group<F>(self, group_fn: F) -> Self
+    where
+        F: FnOnce(QueryBuilder<E>) -> QueryBuilder<E>,
+    {
+        self.push_group(FilterLogic::Or, false, group_fn)
+    }
+
+    pub fn where_not<F>(self, group_fn: F) -> Self
+    where
+        F: FnOnce(QueryBuilder<E>) -> QueryBuilder<E>,
+    {
+        self.push_group(FilterLogic::And, true, group_fn)
+    }
+
+    pub fn or_where_not<F>(self, group_fn: F) -> Self
+    where
+        F: FnOnce(QueryBuilder<E>) -> QueryBuilder<E>,
+    {
+        self.push_group(FilterLogic::Or, true, group_fn)
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.alias = Some(alias.to_string());
+        self
+    }
+
+    pub fn from_subquery<S: Entity>(mut self, query: QueryBuilder<S>, alias: &str) -> Self {
+        self.from_query = Some(Box::new(Subquery::from(&query)));
+        self.alias = Some(alias.to_string());
+        self
+    }
+
+    pub fn join_clause(mut self, join: JoinClause) -> Self {
+        self.joins.push(join);
+        self
+    }
+
+    pub fn join(self, table: &str, left: &str, operator: Operator, right: &str) -> Self {
+        self.join_clause(JoinClause::inner(table).on(left, operator, right))
+    }
+
+    pub fn left_join(self, table: &str, left: &str, operator: Operator, right: &str) -> Self {
+        self.join_clause(JoinClause::left(table).on(left, operator, right))
+    }
+
+    pub fn right_join(self, table: &str, left: &str, operator: Operator, right: &str) -> Self {
+        self.join_clause(JoinClause::right(table).on(left, operator, right))
+    }
+
+    pub fn join_relation(self, relation: &str) -> Self {
+        self.push_relation_join(relation, JoinType::Inner)
+    }
+
+    pub fn left_join_relation(self, relation: &str) -> Self {
+        self.push_relation_join(relation, JoinType::Left)
+    }
+
+    fn push_relation_join(mut self, relation: &str, join_type: JoinType) -> Self {
+        let relationships = E::relationships();
+        let Some(rel) = relationships.get(relation) else {
+            tracing::warn!(
+                "Unknown relationship '{}' on table '{}', join skipped",
+                relation,
+                self.table_name
+            );
+            return self;
+        };
+
+        let base = self.alias.as_deref().unwrap_or(&self.table_name);
+        let joins = rel.join_clauses(base, join_type);
+        self.joins.extend(joins);
         self
     }
 
@@ -123,6 +643,21 @@ impl<E: Entity> QueryBuilder<E> {
         self
     }
 
+    pub fn unfiltered(mut self) -> Self {
+        self.unfiltered = true;
+        self
+    }
+
+    pub fn has_filters(&self) -> bool {
+        fn has_conditions(clauses: &[WhereClause]) -> bool {
+            clauses.iter().any(|clause| match &clause.node {
+                WhereNode::Group(children) => has_conditions(children),
+                _ => true,
+            })
+        }
+        has_conditions(&self.filters)
+    }
+
     pub fn with(mut self, relation: &str) -> Self {
         self.with_relations.push(relation.to_string());
         self
@@ -221,4 +756,22 @@ impl<E: Entity> QueryBuilder<E> {
     {
         repo.find_first_with_query(self).await
     }
+
+    pub async fn update<R>(
+        self,
+        repo: &R,
+        assignments: Vec<crate::domain::query::assignment::Assignment>,
+    ) -> crate::domain::TikalResult<u64>
+    where
+        R: crate::domain::repositories::Repository<E>,
+    {
+        repo.update_where(self, assignments).await
+    }
+
+    pub async fn delete<R>(self, repo: &R) -> crate::domain::TikalResult<u64>
+    where
+        R: crate::domain::repositories::Repository<E>,
+    {
+        repo.delete_where(self).await
+    }
 }
@@ -1,5 +1,6 @@
 use crate::domain::model::Entity;
 use crate::domain::query::builder::QueryBuilder;
+use crate::domain::query::filter::FilterLogic;
 use crate::domain::TikalResult;
 
 pub trait QueryBuilderExt<E: Entity> {
@@ -22,12 +23,8 @@ impl<E: Entity> QueryBuilderExt<E> for QueryBuilder<E> {
         F: FnOnce() -> crate::domain::query::filter::Filter,
     {
         let filter = filter_fn();
-        if !filter.values.is_empty() {
-            self.where_clause(
-                &filter.column,
-                filter.operator,
-                filter.values.clone().into_iter().next().unwrap(),
-            )
+        if !filter.is_empty() {
+            self.where_filter(filter)
         } else {
             self
         }
@@ -38,19 +35,20 @@ impl<E: Entity> QueryBuilderExt<E> for QueryBuilder<E> {
         F: FnOnce() -> crate::domain::query::filter::FilterGroup,
     {
         let filter_group = filter_fn();
-        let mut result = self;
-
-        for filter in filter_group.iter() {
-            if !filter.values.is_empty() {
-                result = result.where_clause(
-                    &filter.column,
-                    filter.operator,
-                    filter.values.clone().into_iter().next().unwrap(),
-                );
+        let logic = filter_group.logic();
+
+        self.where_group(|mut group| {
+            for filter in filter_group.iter() {
+                if filter.is_empty() {
+                    continue;
+                }
+                group = match logic {
+                    FilterLogic::And => group.where_filter(filter.clone()),
+                    FilterLogic::Or => group.or_where_filter(filter.clone()),
+                };
             }
-        }
-
-        result
+            group
+        })
     }
 
     fn order_by_<F>(self, order_fn: F) -> QueryBuilder<E>
@@ -131,12 +129,8 @@ where
     {
         let filter = filter_fn();
         async move {
-            if !filter.values.is_empty() {
-                let query = E::find().where_clause(
-                    &filter.column,
-                    filter.operator,
-                    filter.values.clone().into_iter().next().unwrap(),
-                );
+            if !filter.is_empty() {
+                let query = E::find().where_filter(filter);
                 self.find_with_query(query).await
             } else {
                 Ok(Vec::new())
@@ -153,12 +147,8 @@ where
     {
         let filter = filter_fn();
         async move {
-            if !filter.values.is_empty() {
-                let query = E::find().where_clause(
-                    &filter.column,
-                    filter.operator,
-                    filter.values.clone().into_iter().next().unwrap(),
-                );
+            if !filter.is_empty() {
+                let query = E::find().where_filter(filter);
                 self.find_first_with_query(query).await
             } else {
                 Ok(None)
@@ -203,12 +193,8 @@ where
     {
         let filter = filter_fn();
         async move {
-            if !filter.values.is_empty() {
-                let query = E::find().where_clause(
-                    &filter.column,
-                    filter.operator,
-                    filter.values.clone().into_iter().next().unwrap(),
-                );
+            if !filter.is_empty() {
+                let query = E::find().where_filter(filter);
                 self.count(query).await
             } else {
                 Ok(0)
@@ -225,14 +211,8 @@ where
     {
         let filter = filter_fn();
         async move {
-            if !filter.values.is_empty() {
-                let query = E::find()
-                    .where_clause(
-                        &filter.column,
-                        filter.operator,
-                        filter.values.clone().into_iter().next().unwrap(),
-                    )
-                    .limit(1);
+            if !filter.is_empty() {
+                let query = E::find().where_filter(filter).limit(1);
                 let result = self.find_first_with_query(query).await?;
                 Ok(result.is_some())
             } else {
@@ -53,10 +53,64 @@ impl Filter {
         )
     }
 
+    pub fn not_like(column: &str, value: impl Into<Value>) -> Self {
+        Self::new(column.to_string(), Operator::NotLike, vec![value.into()])
+    }
+
+    pub fn ilike(column: &str, value: impl Into<Value>) -> Self {
+        Self::new(column.to_string(), Operator::ILike, vec![value.into()])
+    }
+
+    pub fn not_in(column: &str, values: Vec<impl Into<Value>>) -> Self {
+        Self::new(
+            column.to_string(),
+            Operator::NotIn,
+            values.into_iter().map(|v| v.into()).collect(),
+        )
+    }
+
+    pub fn between(column: &str, low: impl Into<Value>, high: impl Into<Value>) -> Self {
+        Self::new(
+            column.to_string(),
+            Operator::Between,
+            vec![low.into(), high.into()],
+        )
+    }
+
+    pub fn contains(column: &str, values: Vec<impl Into<Value>>) -> Self {
+        let values = values.into_iter().map(|v| v.into()).collect();
+        Self::new(
+            column.to_string(),
+            Operator::Contains,
+            vec![Value::Array(values)],
+        )
+    }
+
+    pub fn any(column: &str, value: impl Into<Value>) -> Self {
+        Self::new(column.to_string(), Operator::Any, vec![value.into()])
+    }
+
+    pub fn is_null(column: &str) -> Self {
+        Self::new(column.to_string(), Operator::IsNull, Vec::new())
+    }
+
+    pub fn is_not_null(column: &str) -> Self {
+        Self::new(column.to_string(), Operator::IsNotNull, Vec::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && !self.operator.is_unary()
+    }
+
     pub fn to_sql(&self, placeholder_generator: impl Fn(usize) -> String) -> (String, Vec<Value>) {
         let params = self.values.clone();
+        let null_operand = params.first().is_none_or(|v| matches!(v, Value::Null));
 
         match self.operator {
+            Operator::IsNull => (format!("{} IS NULL", self.column), Vec::new()),
+            Operator::IsNotNull => (format!("{} IS NOT NULL", self.column), Vec::new()),
+            Operator::Eq if null_operand => (format!("{} IS NULL", self.column), Vec::new()),
+            Operator::Ne if null_operand => (format!("{} IS NOT NULL", self.column), Vec::new()),
             Operator::Eq => (
                 format!("{} = {}", self.column, placeholder_generator(0)),
                 params,
@@ -85,12 +139,55 @@ impl Filter {
                 format!("{} LIKE {}", self.column, placeholder_generator(0)),
                 params,
             ),
-            Operator::In => {
+            Operator::NotLike => (
+                format!("{} NOT LIKE {}", self.column, placeholder_generator(0)),
+                params,
+            ),
+            Operator::ILike => (
+                format!(
+                    "LOWER({}) LIKE LOWER({})",
+                    self.column,
+                    placeholder_generator(0)
+                ),
+                params,
+            ),
+            // PostgreSQL array syntax; the query builder adapts these per dialect
+            Operator::Contains => (
+                format!("{} @> {}", self.column, placeholder_generator(0)),
+                params,
+            ),
+            Operator::Any => (
+                format!("{} = ANY({})", placeholder_generator(0), self.column),
+                params,
+            ),
+            Operator::Between => (
+                format!(
+                    "{} BETWEEN {} AND {}",
+                    self.column,
+                    placeholder_generator(0),
+                    placeholder_generator(1)
+                ),
+                params,
+            ),
+            Operator::In | Operator::NotIn if params.is_empty() => {
+                let sql = if self.operator == Operator::In {
+                    "1 = 0"
+                } else {
+                    "1 = 1"
+                };
+                (sql.to_string(), params)
+            }
+            Operator::In | Operator::NotIn => {
                 let placeholders: Vec<String> = (0..params.len())
                     .map(|i| placeholder_generator(i))
                     .collect();
+                let keyword = if self.operator == Operator::In {
+                    "IN"
+                } else {
+                    "NOT IN"
+                };
                 (
-                    format!("{} IN ({})", self.column, placeholders.join(", ")),
+                    format!("{} {} ({})", self.column, keyword, placeholders.join(", ")),
                     params,
                 )
             }
@@ -104,7 +201,7 @@ pub struct FilterBuilder {
     logic: FilterLogic,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FilterLogic {
     And,
     Or,
@@ -186,6 +283,10 @@ impl FilterGroup {
         self.filters.iter()
     }
 
+    pub fn logic(&self) -> FilterLogic {
+        self.logic
+    }
+
     pub fn to_sql(&self, placeholder_generator: impl Fn(usize) -> String) -> (String, Vec<Value>) {
         if self.filters.is_empty() {
             return (String::new(), Vec::new());
@@ -234,3 +335,11 @@ pub fn column_in(column: &str, values: Vec<impl Into<Value>>) -> Filter {
 pub fn column_like(column: &str, pattern: impl Into<Value>) -> Filter {
     Filter::like(column, pattern)
 }
+
+pub fn column_is_null(column: &str) -> Filter {
+    Filter::is_null(column)
+}
+
+pub fn column_between(column: &str, low: impl Into<Value>, high: impl Into<Value>) -> Filter {
+    Filter::between(column, low, high)
+}
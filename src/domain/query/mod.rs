@@ -1,9 +1,11 @@
 pub mod aggregate;
+pub mod assignment;
 pub mod builder;
 pub mod extensions;
 pub mod filter;
 pub mod order;
 
+pub use assignment::{Assignment, AssignmentValue};
 pub use builder::QueryBuilder;
 pub use filter::{Filter, FilterBuilder, FilterGroup};
 pub use order::{OrderBuilder, OrderClause, OrderGroup};
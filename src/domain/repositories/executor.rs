@@ -1,4 +1,4 @@
-use crate::domain::repositories::types::{DriverInfo, QueryStats};
+use crate::domain::repositories::types::{DriverInfo, QueryStats, TransactionOptions};
 use crate::domain::value_objects::Value;
 use crate::domain::TikalResult;
 use async_trait::async_trait;
@@ -42,6 +42,18 @@ pub trait QueryExecutor: Send + Sync {
 
     async fn begin_transaction(&self) -> TikalResult<Box<dyn Transaction>>;
 
+    async fn begin_transaction_with(
+        &self,
+        options: TransactionOptions,
+    ) -> TikalResult<Box<dyn Transaction>> {
+        if options.is_default() {
+            return self.begin_transaction().await;
+        }
+        Err(crate::domain::error::TikalError::not_implemented(
+            "transaction options",
+        ))
+    }
+
     async fn ping(&self) -> TikalResult<bool>;
 
     fn driver_info(&self) -> DriverInfo;
@@ -84,6 +96,104 @@ pub trait Transaction: Send {
     async fn commit(self: Box<Self>) -> TikalResult<()>;
 
     async fn rollback(self: Box<Self>) -> TikalResult<()>;
+
+    async fn begin<'a>(&'a mut self) -> TikalResult<Box<dyn Transaction + 'a>>;
+
+    fn depth(&self) -> usize {
+        0
+    }
+
+    async fn savepoint(&mut self, name: &str) -> TikalResult<()> {
+        if !is_valid_savepoint_name(name) {
+            return Err(invalid_savepoint_name(name));
+        }
+        self.execute(&format!("SAVEPOINT {}", name), Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    async fn rollback_to(&mut self, name: &str) -> TikalResult<()> {
+        if !is_valid_savepoint_name(name) {
+            return Err(invalid_savepoint_name(name));
+        }
+        self.execute(&format!("ROLLBACK TO SAVEPOINT {}", name), Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    async fn release(&mut self, name: &str) -> TikalResult<()> {
+        if !is_valid_savepoint_name(name) {
+            return Err(invalid_savepoint_name(name));
+        }
+        self.execute(&format!("RELEASE SAVEPOINT {}", name), Vec::new())
+            .await?;
+        Ok(())
+    }
+}
+
+pub fn is_valid_savepoint_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn invalid_savepoint_name(name: &str) -> crate::domain::error::TikalError {
+    crate::domain::error::TikalError::validation(
+        "savepoint",
+        &format!("'{}' is not a valid savepoint name", name),
+    )
+}
+
+pub struct Savepoint<'a> {
+    parent: &'a mut dyn Transaction,
+    name: String,
+    depth: usize,
+}
+
+impl<'a> Savepoint<'a> {
+    pub async fn begin(parent: &'a mut dyn Transaction) -> TikalResult<Self> {
+        let depth = parent.depth() + 1;
+        let name = format!("tikal_sp_{}", depth);
+        parent.savepoint(&name).await?;
+        Ok(Self {
+            parent,
+            name,
+            depth,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> Transaction for Savepoint<'a> {
+    async fn fetch_all(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> TikalResult<Vec<HashMap<String, Value>>> {
+        self.parent.fetch_all(sql, params).await
+    }
+
+    async fn execute(&mut self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
+        self.parent.execute(sql, params).await
+    }
+
+    async fn commit(self: Box<Self>) -> TikalResult<()> {
+        self.parent.release(&self.name).await
+    }
+
+    async fn rollback(self: Box<Self>) -> TikalResult<()> {
+        self.parent.rollback_to(&self.name).await?;
+        self.parent.release(&self.name).await
+    }
+
+    async fn begin<'b>(&'b mut self) -> TikalResult<Box<dyn Transaction + 'b>> {
+        Ok(Box::new(Savepoint::begin(self).await?))
+    }
+
+    fn depth(&self) -> usize {
+        self.depth
+    }
 }
 
 #[async_trait]
@@ -2,6 +2,7 @@ pub mod executor;
 pub mod types;
 
 use crate::domain::model::Entity;
+use crate::domain::query::assignment::Assignment;
 use crate::domain::query::builder::QueryBuilder;
 use crate::domain::value_objects::Value;
 use crate::domain::TikalResult;
@@ -19,11 +20,32 @@ pub trait Repository<E: Entity>: Send + Sync {
     async fn min(&self, query: QueryBuilder<E>, field: &str) -> TikalResult<Option<Value>>;
     async fn max(&self, query: QueryBuilder<E>, field: &str) -> TikalResult<Option<Value>>;
     async fn save(&self, entity: &E) -> TikalResult<u64>;
+    async fn save_returning(&self, entity: &E) -> TikalResult<E>;
     async fn save_many(&self, entities: &[E]) -> TikalResult<u64>;
+    async fn save_many_returning_keys(&self, entities: &[E]) -> TikalResult<Vec<Value>>;
+    async fn upsert(
+        &self,
+        entity: &E,
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> TikalResult<u64>;
+    async fn upsert_many(
+        &self,
+        entities: &[E],
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> TikalResult<u64>;
+    async fn insert_or_ignore(&self, entity: &E) -> TikalResult<u64>;
     async fn update(&self, entity: &E) -> TikalResult<u64>;
     async fn update_many(&self, entities: &[E]) -> TikalResult<u64>;
     async fn delete(&self, entity: &E) -> TikalResult<u64>;
     async fn delete_many(&self, entities: &[E]) -> TikalResult<u64>;
+    async fn update_where(
+        &self,
+        query: QueryBuilder<E>,
+        assignments: Vec<Assignment>,
+    ) -> TikalResult<u64>;
+    async fn delete_where(&self, query: QueryBuilder<E>) -> TikalResult<u64>;
     async fn execute_raw(&self, sql: &str, params: Vec<Value>) -> TikalResult<u64>;
     async fn query_raw(
         &self,
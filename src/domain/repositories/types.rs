@@ -3,6 +3,7 @@ pub struct DriverInfo {
     pub name: String,
     pub version: String,
     pub driver_type: DriverType,
+    pub max_connections: u32,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,3 +21,61 @@ pub struct QueryStats {
     pub success: bool,
     pub error_message: Option<String>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionOptions {
+    pub isolation: Option<IsolationLevel>,
+    pub read_only: bool,
+    pub deferrable: bool,
+    // Takes the write lock when the transaction starts (SQLite only)
+    pub immediate: bool,
+}
+
+impl TransactionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn isolation(mut self, level: IsolationLevel) -> Self {
+        self.isolation = Some(level);
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    pub fn deferrable(mut self) -> Self {
+        self.deferrable = true;
+        self
+    }
+
+    pub fn immediate(mut self) -> Self {
+        self.immediate = true;
+        self
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
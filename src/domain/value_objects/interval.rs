@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+
+// Months and days are kept apart from the time part because their length
+// depends on the date they are added to, as with PostgreSQL's INTERVAL.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    pub fn months(months: i32) -> Self {
+        Self::new(months, 0, 0)
+    }
+
+    pub fn days(days: i32) -> Self {
+        Self::new(0, days, 0)
+    }
+
+    pub fn seconds(seconds: i64) -> Self {
+        Self::new(0, 0, seconds * MICROS_PER_SECOND)
+    }
+}
+
+impl From<chrono::Duration> for Interval {
+    fn from(duration: chrono::Duration) -> Self {
+        // Saturates past the range of i64 microseconds, roughly 292,000 years
+        let microseconds = match duration.num_microseconds() {
+            Some(microseconds) => microseconds,
+            None if duration < chrono::Duration::zero() => i64::MIN,
+            None => i64::MAX,
+        };
+        Self::new(0, 0, microseconds)
+    }
+}
+
+// ISO 8601 duration, e.g. P1Y2M3DT4H5M6.5S, which PostgreSQL also accepts as input
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "P")?;
+        if self.months != 0 {
+            write!(f, "{}M", self.months)?;
+        }
+        if self.days != 0 || (self.months == 0 && self.microseconds == 0) {
+            write!(f, "{}D", self.days)?;
+        }
+        if self.microseconds != 0 {
+            let sign = if self.microseconds < 0 { "-" } else { "" };
+            let micros = self.microseconds.unsigned_abs();
+            let seconds = micros / MICROS_PER_SECOND as u64;
+            let fraction = micros % MICROS_PER_SECOND as u64;
+            if fraction == 0 {
+                write!(f, "T{}{}S", sign, seconds)?;
+            } else {
+                let fraction = format!("{:06}", fraction);
+                write!(
+                    f,
+                    "T{}{}.{}S",
+                    sign,
+                    seconds,
+                    fraction.trim_end_matches('0')
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid ISO 8601 interval: '{}'", s);
+        let rest = s.trim().strip_prefix('P').ok_or_else(invalid)?;
+
+        let mut interval = Interval::default();
+        let mut in_time = false;
+        let mut number = String::new();
+        for c in rest.chars() {
+            match c {
+                'T' if number.is_empty() => in_time = true,
+                '0'..='9' | '.' | '-' | '+' => number.push(c),
+                _ => {
+                    let value: f64 = number.parse().map_err(|_| invalid())?;
+                    number.clear();
+                    match (in_time, c) {
+                        (false, 'Y') => interval.months += (value * 12.0) as i32,
+                        (false, 'M') => interval.months += value as i32,
+                        (false, 'W') => interval.days += (value * 7.0) as i32,
+                        (false, 'D') => interval.days += value as i32,
+                        (true, 'H') => interval.microseconds += (value * 3_600e6).round() as i64,
+                        (true, 'M') => interval.microseconds += (value * 60e6).round() as i64,
+                        (true, 'S') => interval.microseconds += (value * 1e6).round() as i64,
+                        _ => return Err(invalid()),
+                    }
+                }
+            }
+        }
+        if !number.is_empty() {
+            return Err(invalid());
+        }
+        Ok(interval)
+    }
+}
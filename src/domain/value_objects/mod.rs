@@ -1,3 +1,5 @@
+pub mod interval;
 pub mod value;
 
+pub use interval::Interval;
 pub use value::{FromValue, Value};
@@ -1,5 +1,8 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use super::interval::Interval;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(untagged)]
@@ -13,6 +16,12 @@ pub enum Value {
     Json(serde_json::Value),
     Binary(Vec<u8>),
     NaiveDateTime(NaiveDateTime),
+    Decimal(Decimal),
+    Uuid(Uuid),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Interval(Interval),
+    Array(Vec<Value>),
 }
 
 impl std::hash::Hash for Value {
@@ -27,6 +36,12 @@ impl std::hash::Hash for Value {
             Value::Json(j) => j.to_string().hash(state),
             Value::Binary(b) => b.hash(state),
             Value::NaiveDateTime(ndt) => ndt.hash(state),
+            Value::Decimal(d) => d.hash(state),
+            Value::Uuid(u) => u.hash(state),
+            Value::Date(d) => d.hash(state),
+            Value::Time(t) => t.hash(state),
+            Value::Interval(i) => i.hash(state),
+            Value::Array(items) => items.hash(state),
         }
     }
 }
@@ -43,6 +58,40 @@ impl std::fmt::Display for Value {
             Value::Json(j) => write!(f, "'{}'", j.to_string().replace("'", "''")),
             Value::Binary(b) => write!(f, "X'{}'", hex::encode(b)),
             Value::NaiveDateTime(ndt) => write!(f, "'{}'", ndt.format("%Y-%m-%d %H:%M:%S")),
+            Value::Decimal(d) => write!(f, "{}", d),
+            Value::Uuid(u) => write!(f, "'{}'", u),
+            Value::Date(d) => write!(f, "'{}'", d.format("%Y-%m-%d")),
+            Value::Time(t) => write!(f, "'{}'", t.format("%H:%M:%S%.f")),
+            Value::Interval(i) => write!(f, "'{}'", i),
+            Value::Array(_) => write!(f, "'{}'", self.array_json().to_string().replace("'", "''")),
+        }
+    }
+}
+
+impl Value {
+    // Arrays are stored as JSON by drivers without a native array type
+    pub fn array_json(&self) -> serde_json::Value {
+        match self {
+            Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(Value::array_json).collect())
+            }
+            other => serde_json::to_value(other).unwrap_or(serde_json::Value::Null),
+        }
+    }
+
+    fn from_json(json: serde_json::Value) -> Value {
+        match json {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Value::Int(i),
+                None => Value::from(n.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(s) => Value::Text(s),
+            serde_json::Value::Array(items) => {
+                Value::Array(items.into_iter().map(Value::from_json).collect())
+            }
+            object => Value::Json(object),
         }
     }
 }
@@ -101,6 +150,42 @@ impl From<NaiveDateTime> for Value {
     }
 }
 
+impl From<Decimal> for Value {
+    fn from(d: Decimal) -> Self {
+        Value::Decimal(d)
+    }
+}
+
+impl From<Uuid> for Value {
+    fn from(u: Uuid) -> Self {
+        Value::Uuid(u)
+    }
+}
+
+impl From<NaiveDate> for Value {
+    fn from(d: NaiveDate) -> Self {
+        Value::Date(d)
+    }
+}
+
+impl From<NaiveTime> for Value {
+    fn from(t: NaiveTime) -> Self {
+        Value::Time(t)
+    }
+}
+
+impl From<Interval> for Value {
+    fn from(i: Interval) -> Self {
+        Value::Interval(i)
+    }
+}
+
+impl From<chrono::Duration> for Value {
+    fn from(d: chrono::Duration) -> Self {
+        Value::Interval(d.into())
+    }
+}
+
 pub trait FromValue: Sized {
     fn from_value(v: Value) -> Result<Self, String>;
 }
@@ -223,3 +308,81 @@ impl FromValue for Vec<u8> {
         }
     }
 }
+
+// Drivers without a native type store these as text, so text is parsed back.
+impl FromValue for Decimal {
+    fn from_value(v: Value) -> Result<Self, String> {
+        match v {
+            Value::Decimal(d) => Ok(d),
+            Value::Int(i) => Ok(Decimal::from(i)),
+            Value::Text(s) => s
+                .parse()
+                .map_err(|e| format!("Invalid Decimal '{}': {}", s, e)),
+            _ => Err("Expected Decimal".to_string()),
+        }
+    }
+}
+
+impl FromValue for Uuid {
+    fn from_value(v: Value) -> Result<Self, String> {
+        match v {
+            Value::Uuid(u) => Ok(u),
+            Value::Text(s) => {
+                Uuid::parse_str(&s).map_err(|e| format!("Invalid Uuid '{}': {}", s, e))
+            }
+            Value::Binary(b) => Uuid::from_slice(&b).map_err(|e| format!("Invalid Uuid: {}", e)),
+            _ => Err("Expected Uuid".to_string()),
+        }
+    }
+}
+
+impl FromValue for NaiveDate {
+    fn from_value(v: Value) -> Result<Self, String> {
+        match v {
+            Value::Date(d) => Ok(d),
+            Value::Text(s) => NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid Date '{}': {}", s, e)),
+            _ => Err("Expected Date".to_string()),
+        }
+    }
+}
+
+impl FromValue for NaiveTime {
+    fn from_value(v: Value) -> Result<Self, String> {
+        match v {
+            Value::Time(t) => Ok(t),
+            Value::Text(s) => NaiveTime::parse_from_str(&s, "%H:%M:%S%.f")
+                .map_err(|e| format!("Invalid Time '{}': {}", s, e)),
+            _ => Err("Expected Time".to_string()),
+        }
+    }
+}
+
+impl FromValue for Interval {
+    fn from_value(v: Value) -> Result<Self, String> {
+        match v {
+            Value::Interval(i) => Ok(i),
+            Value::Text(s) => s.parse(),
+            _ => Err("Expected Interval".to_string()),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(v: Value) -> Result<Self, String> {
+        let items = match v {
+            Value::Array(items) => items,
+            Value::Json(serde_json::Value::Array(items)) => {
+                items.into_iter().map(Value::from_json).collect()
+            }
+            Value::Text(s) => match serde_json::from_str(&s) {
+                Ok(serde_json::Value::Array(items)) => {
+                    items.into_iter().map(Value::from_json).collect()
+                }
+                _ => return Err(format!("Invalid Array '{}'", s)),
+            },
+            _ => return Err("Expected Array".to_string()),
+        };
+        items.into_iter().map(T::from_value).collect()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::repositories::executor::{QueryExecutor, Transaction};
+use crate::domain::repositories::executor::{QueryExecutor, Savepoint, Transaction};
 use crate::domain::repositories::types::{DriverInfo, DriverType};
 use crate::domain::value_objects::Value;
 use crate::domain::{TikalError, TikalResult};
@@ -167,6 +167,7 @@ impl QueryExecutor for MockExecutor {
             name: "Mock".to_string(),
             version: "1.0.0".to_string(),
             driver_type: DriverType::SQLite,
+            max_connections: 1,
         }
     }
 }
@@ -314,4 +315,8 @@ impl Transaction for MockTransaction {
         self.rolled_back = true;
         Ok(())
     }
+
+    async fn begin<'a>(&'a mut self) -> TikalResult<Box<dyn Transaction + 'a>> {
+        Ok(Box::new(Savepoint::begin(self).await?))
+    }
 }
@@ -1,5 +1,6 @@
+use crate::domain::repositories::types::{DriverType, IsolationLevel, TransactionOptions};
 use crate::domain::value_objects::Value;
-use crate::domain::TikalResult;
+use crate::domain::{TikalError, TikalResult};
 use sqlx::{Column, Row};
 use std::collections::HashMap;
 
@@ -20,6 +21,99 @@ pub trait ParameterBinder<'q, DB: sqlx::Database> {
     }
 }
 
+// Options a dialect cannot honour are refused rather than dropped
+pub fn transaction_statements(
+    driver_type: &DriverType,
+    options: &TransactionOptions,
+) -> TikalResult<(Vec<String>, String)> {
+    if options.deferrable && *driver_type != DriverType::PostgreSQL {
+        return Err(TikalError::validation(
+            "deferrable",
+            "DEFERRABLE transactions are only supported on PostgreSQL",
+        ));
+    }
+    if options.immediate && *driver_type != DriverType::SQLite {
+        return Err(TikalError::validation(
+            "immediate",
+            "IMMEDIATE transactions are only supported on SQLite",
+        ));
+    }
+
+    match driver_type {
+        DriverType::PostgreSQL => {
+            let mut sql = String::from("BEGIN");
+            if let Some(isolation) = options.isolation {
+                sql.push_str(&format!(" ISOLATION LEVEL {}", isolation.as_sql()));
+            }
+            if options.read_only {
+                sql.push_str(" READ ONLY");
+            }
+            if options.deferrable {
+                sql.push_str(" DEFERRABLE");
+            }
+            Ok((Vec::new(), sql))
+        }
+        DriverType::MySQL => {
+            let setup = options
+                .isolation
+                .map(|isolation| {
+                    vec![format!(
+                        "SET TRANSACTION ISOLATION LEVEL {}",
+                        isolation.as_sql()
+                    )]
+                })
+                .unwrap_or_default();
+            let sql = if options.read_only {
+                "START TRANSACTION READ ONLY"
+            } else {
+                "START TRANSACTION"
+            };
+            Ok((setup, sql.to_string()))
+        }
+        // SQLite transactions are always serializable, the choice is when the
+        // write lock is taken. Immediate writers take it up front so they never
+        // fail with SQLITE_BUSY when upgrading from a read lock.
+        DriverType::SQLite => {
+            if options
+                .isolation
+                .is_some_and(|isolation| isolation != IsolationLevel::Serializable)
+            {
+                return Err(TikalError::validation(
+                    "isolation",
+                    "SQLite transactions are always SERIALIZABLE",
+                ));
+            }
+            // query_only belongs to the connection and would outlive the
+            // transaction in the pool, so there is no read-only transaction
+            if options.read_only {
+                return Err(TikalError::validation(
+                    "read_only",
+                    "READ ONLY transactions are not supported on SQLite",
+                ));
+            }
+            let sql = if options.immediate {
+                "BEGIN IMMEDIATE"
+            } else {
+                "BEGIN"
+            };
+            Ok((Vec::new(), sql.to_string()))
+        }
+    }
+}
+
+// rust_decimal holds 28 significant digits and no NaN, while NUMERIC and
+// DECIMAL columns can hold more; reading those as NULL would lose data silently
+pub fn unrepresentable_decimal(column_name: &str, error: sqlx::Error) -> TikalError {
+    TikalError::mapping(
+        column_name,
+        &format!("numeric value does not fit in a Decimal: {}", error),
+    )
+}
+
+pub trait AffectedRows {
+    fn affected_rows(&self) -> u64;
+}
+
 pub trait RowMapper<DB: sqlx::Database> {
     fn map_value(row: &DB::Row, column_name: &str) -> TikalResult<Value>
     where
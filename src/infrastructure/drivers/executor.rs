@@ -1,6 +1,6 @@
 use crate::domain::repositories::executor::QueryExecutor as DomainQueryExecutor;
 use crate::domain::repositories::executor::Transaction as DomainTransaction;
-use crate::domain::repositories::types::DriverInfo;
+use crate::domain::repositories::types::{DriverInfo, TransactionOptions};
 use crate::domain::value_objects::Value;
 use crate::domain::TikalResult;
 use async_trait::async_trait;
@@ -24,6 +24,7 @@ where
     DB: sqlx::Database,
     B: for<'q> super::ParameterBinder<'q, DB> + Send + Sync + 'static,
     M: super::RowMapper<DB> + Send + Sync + 'static,
+    DB::QueryResult: super::AffectedRows,
     for<'r> &'r str: sqlx::ColumnIndex<DB::Row>,
     for<'q> <DB as sqlx::Database>::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
     for<'c> &'c mut <DB as sqlx::Database>::Connection: sqlx::Executor<'c, Database = DB>,
@@ -67,8 +68,8 @@ where
     async fn execute(&self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
         let query = sqlx::query(sql);
         let query = B::bind_params(query, params);
-        query.execute(&self.pool).await?;
-        Ok(1)
+        let result = query.execute(&self.pool).await?;
+        Ok(super::AffectedRows::affected_rows(&result))
     }
 
     async fn execute_with_rows(&self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
@@ -80,6 +81,24 @@ where
         Ok(Box::new(super::DatabaseTransaction::<DB, B, M>::new(tx)))
     }
 
+    async fn begin_transaction_with(
+        &self,
+        options: TransactionOptions,
+    ) -> TikalResult<Box<dyn DomainTransaction>> {
+        if options.is_default() {
+            return self.begin_transaction().await;
+        }
+
+        let (setup, begin) =
+            super::common::transaction_statements(&self.driver_info.driver_type, &options)?;
+        let mut conn = self.pool.acquire().await?;
+        for sql in setup {
+            sqlx::query(&sql).execute(&mut *conn).await?;
+        }
+        let tx = sqlx::Transaction::begin(conn, Some(begin.into())).await?;
+        Ok(Box::new(super::DatabaseTransaction::<DB, B, M>::new(tx)))
+    }
+
     async fn ping(&self) -> TikalResult<bool> {
         Ok(self.fetch_all("SELECT 1", vec![]).await.is_ok())
     }
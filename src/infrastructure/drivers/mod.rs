@@ -2,10 +2,12 @@ pub mod common;
 pub mod executor;
 pub mod traits;
 pub mod transaction;
+pub mod transaction_executor;
 
-pub use common::{ParameterBinder, RowMapper};
+pub use common::{AffectedRows, ParameterBinder, RowMapper};
 pub use executor::DatabaseExecutor;
 pub use transaction::DatabaseTransaction;
+pub use transaction_executor::TransactionExecutor;
 
 #[cfg(feature = "mysql")]
 pub mod mysql;
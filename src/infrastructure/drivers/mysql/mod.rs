@@ -15,6 +15,7 @@ impl MySqlExecutor {
             name: "MySQL".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             driver_type: DriverType::MySQL,
+            max_connections: pool.options().get_max_connections(),
         };
         Self {
             pool,
@@ -25,6 +26,12 @@ impl MySqlExecutor {
     }
 }
 
+impl super::AffectedRows for sqlx::mysql::MySqlQueryResult {
+    fn affected_rows(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
 impl<'q> super::ParameterBinder<'q, MySql> for MySqlBinder {
     fn bind_param(
         query: sqlx::query::Query<'q, MySql, sqlx::mysql::MySqlArguments>,
@@ -40,13 +47,31 @@ impl<'q> super::ParameterBinder<'q, MySql> for MySqlBinder {
             Value::Json(j) => query.bind(j),
             Value::Binary(b) => query.bind(b),
             Value::NaiveDateTime(ndt) => query.bind(ndt),
+            Value::Decimal(d) => query.bind(d),
+            // Stored as CHAR(36) and VARCHAR, see the MySQL type mappings
+            Value::Uuid(u) => query.bind(u.to_string()),
+            Value::Date(d) => query.bind(d),
+            Value::Time(t) => query.bind(t),
+            Value::Interval(i) => query.bind(i.to_string()),
+            // MySQL has no array type, so arrays live in JSON columns
+            Value::Array(_) => query.bind(param.array_json()),
         }
     }
 }
 
 impl super::RowMapper<MySql> for MySqlMapper {
     fn map_value(row: &sqlx::mysql::MySqlRow, column_name: &str) -> TikalResult<Value> {
-        use sqlx::Row;
+        use sqlx::{Row, TypeInfo, ValueRef};
+
+        if let Ok(raw) = row.try_get_raw(column_name)
+            && !raw.is_null()
+            && raw.type_info().name() == "DECIMAL"
+        {
+            return row
+                .try_get::<rust_decimal::Decimal, _>(column_name)
+                .map(Value::Decimal)
+                .map_err(|e| super::common::unrepresentable_decimal(column_name, e));
+        }
 
         if let Ok(value) = row.try_get::<chrono::DateTime<chrono::Utc>, _>(column_name) {
             return Ok(Value::DateTime(value));
@@ -54,9 +79,23 @@ impl super::RowMapper<MySql> for MySqlMapper {
         if let Ok(value) = row.try_get::<chrono::NaiveDateTime, _>(column_name) {
             return Ok(Value::NaiveDateTime(value));
         }
+        if let Ok(value) = row.try_get::<chrono::NaiveDate, _>(column_name) {
+            return Ok(Value::Date(value));
+        }
+        if let Ok(value) = row.try_get::<chrono::NaiveTime, _>(column_name) {
+            return Ok(Value::Time(value));
+        }
+        if let Ok(value) = row.try_get::<rust_decimal::Decimal, _>(column_name) {
+            return Ok(Value::Decimal(value));
+        }
         if let Ok(value) = row.try_get::<i64, _>(column_name) {
             return Ok(Value::Int(value));
         }
+        if let Ok(value) = row.try_get::<u64, _>(column_name)
+            && let Ok(value) = i64::try_from(value)
+        {
+            return Ok(Value::Int(value));
+        }
         if let Ok(value) = row.try_get::<f64, _>(column_name) {
             return Ok(Value::Float(ordered_float::OrderedFloat(value)));
         }
@@ -1,7 +1,9 @@
 use crate::domain::repositories::types::{DriverInfo, DriverType};
-use crate::domain::value_objects::Value;
+use crate::domain::value_objects::{Interval, Value};
 use crate::domain::TikalResult;
-use sqlx::Postgres;
+use sqlx::postgres::types::PgInterval;
+use sqlx::postgres::PgTypeKind;
+use sqlx::{Postgres, TypeInfo, ValueRef};
 
 pub struct PostgresBinder;
 pub struct PostgresMapper;
@@ -15,6 +17,7 @@ impl PostgresExecutor {
             name: "PostgreSQL".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             driver_type: DriverType::PostgreSQL,
+            max_connections: pool.options().get_max_connections(),
         };
         Self {
             pool,
@@ -25,13 +28,19 @@ impl PostgresExecutor {
     }
 }
 
+impl super::AffectedRows for sqlx::postgres::PgQueryResult {
+    fn affected_rows(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
 impl<'q> super::ParameterBinder<'q, Postgres> for PostgresBinder {
     fn bind_param(
         query: sqlx::query::Query<'q, Postgres, sqlx::postgres::PgArguments>,
         param: Value,
     ) -> sqlx::query::Query<'q, Postgres, sqlx::postgres::PgArguments> {
         match param {
-            Value::Null => query.bind(None::<String>),
+            Value::Null => query.bind(UntypedNull),
             Value::Text(s) => query.bind(s),
             Value::Int(i) => query.bind(i),
             Value::Float(f) => query.bind(f.into_inner()),
@@ -40,20 +49,157 @@ impl<'q> super::ParameterBinder<'q, Postgres> for PostgresBinder {
             Value::Json(j) => query.bind(j),
             Value::Binary(b) => query.bind(b),
             Value::NaiveDateTime(ndt) => query.bind(ndt),
+            Value::Decimal(d) => query.bind(d),
+            Value::Uuid(u) => query.bind(u),
+            Value::Date(d) => query.bind(d),
+            Value::Time(t) => query.bind(t),
+            Value::Interval(i) => query.bind(PgInterval {
+                months: i.months,
+                days: i.days,
+                microseconds: i.microseconds,
+            }),
+            Value::Array(items) => bind_array(query, items),
         }
     }
 }
 
+// Arrays bind as a native array of their elements' type; mixed elements, and
+// types without an array binding here, fall back to JSON
+fn bind_array<'q>(
+    query: sqlx::query::Query<'q, Postgres, sqlx::postgres::PgArguments>,
+    items: Vec<Value>,
+) -> sqlx::query::Query<'q, Postgres, sqlx::postgres::PgArguments> {
+    let first = items.iter().find(|item| !matches!(item, Value::Null));
+    let homogeneous = items.iter().all(|item| {
+        matches!(item, Value::Null)
+            || first
+                .is_none_or(|first| std::mem::discriminant(first) == std::mem::discriminant(item))
+    });
+    if !homogeneous {
+        return query.bind(Value::Array(items).array_json());
+    }
+
+    match first {
+        Some(Value::Int(_)) => query.bind(elements(&items, |item| match item {
+            Value::Int(i) => Some(*i),
+            _ => None,
+        })),
+        Some(Value::Float(_)) => query.bind(elements(&items, |item| match item {
+            Value::Float(f) => Some(f.into_inner()),
+            _ => None,
+        })),
+        Some(Value::Bool(_)) => query.bind(elements(&items, |item| match item {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        })),
+        Some(Value::Decimal(_)) => query.bind(elements(&items, |item| match item {
+            Value::Decimal(d) => Some(*d),
+            _ => None,
+        })),
+        Some(Value::Uuid(_)) => query.bind(elements(&items, |item| match item {
+            Value::Uuid(u) => Some(*u),
+            _ => None,
+        })),
+        Some(Value::Date(_)) => query.bind(elements(&items, |item| match item {
+            Value::Date(d) => Some(*d),
+            _ => None,
+        })),
+        Some(Value::Time(_)) => query.bind(elements(&items, |item| match item {
+            Value::Time(t) => Some(*t),
+            _ => None,
+        })),
+        Some(Value::DateTime(_)) => query.bind(elements(&items, |item| match item {
+            Value::DateTime(dt) => Some(*dt),
+            _ => None,
+        })),
+        Some(Value::NaiveDateTime(_)) => query.bind(elements(&items, |item| match item {
+            Value::NaiveDateTime(ndt) => Some(*ndt),
+            _ => None,
+        })),
+        // Empty and all-NULL arrays bind as text[]
+        Some(Value::Text(_)) | None => query.bind(elements(&items, |item| match item {
+            Value::Text(s) => Some(s.clone()),
+            _ => None,
+        })),
+        Some(_) => query.bind(Value::Array(items).array_json()),
+    }
+}
+
+fn elements<T>(items: &[Value], extract: impl Fn(&Value) -> Option<T>) -> Vec<Option<T>> {
+    items.iter().map(extract).collect()
+}
+
+// A NULL bound as TEXT is rejected by columns of other types, so the parameter
+// is left untyped for the server to infer from where it is used
+struct UntypedNull;
+
+impl sqlx::Type<Postgres> for UntypedNull {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_oid(sqlx::postgres::types::Oid(0))
+    }
+}
+
+impl sqlx::Encode<'_, Postgres> for UntypedNull {
+    fn encode_by_ref(
+        &self,
+        _buf: &mut sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        Ok(sqlx::encode::IsNull::Yes)
+    }
+}
+
 impl super::RowMapper<Postgres> for PostgresMapper {
     fn map_value(row: &sqlx::postgres::PgRow, column_name: &str) -> TikalResult<Value> {
         use sqlx::Row;
 
+        if let Ok(raw) = row.try_get_raw(column_name) {
+            if raw.is_null() {
+                return Ok(Value::Null);
+            }
+            match raw.type_info().kind() {
+                // Enum labels arrive as text but are not text-compatible for try_get
+                PgTypeKind::Enum(_) => {
+                    return Ok(row
+                        .try_get_unchecked::<String, _>(column_name)
+                        .map(Value::Text)
+                        .unwrap_or(Value::Null));
+                }
+                PgTypeKind::Array(_) if raw.type_info().name() == "NUMERIC[]" => {
+                    return row
+                        .try_get::<Vec<Option<rust_decimal::Decimal>>, _>(column_name)
+                        .map(array_value)
+                        .map_err(|e| super::common::unrepresentable_decimal(column_name, e));
+                }
+                PgTypeKind::Array(_) => return Ok(map_array(row, column_name)),
+                _ if raw.type_info().name() == "NUMERIC" => {
+                    return row
+                        .try_get::<rust_decimal::Decimal, _>(column_name)
+                        .map(Value::Decimal)
+                        .map_err(|e| super::common::unrepresentable_decimal(column_name, e));
+                }
+                _ => {}
+            }
+        }
+
         if let Ok(value) = row.try_get::<chrono::DateTime<chrono::Utc>, _>(column_name) {
             return Ok(Value::DateTime(value));
         }
         if let Ok(value) = row.try_get::<chrono::NaiveDateTime, _>(column_name) {
             return Ok(Value::NaiveDateTime(value));
         }
+        if let Ok(value) = row.try_get::<chrono::NaiveDate, _>(column_name) {
+            return Ok(Value::Date(value));
+        }
+        if let Ok(value) = row.try_get::<chrono::NaiveTime, _>(column_name) {
+            return Ok(Value::Time(value));
+        }
+        if let Ok(value) = row.try_get::<PgInterval, _>(column_name) {
+            return Ok(Value::Interval(Interval::new(
+                value.months,
+                value.days,
+                value.microseconds,
+            )));
+        }
         if let Ok(value) = row.try_get::<i64, _>(column_name) {
             return Ok(Value::Int(value));
         }
@@ -63,6 +209,12 @@ impl super::RowMapper<Postgres> for PostgresMapper {
         if let Ok(value) = row.try_get::<f64, _>(column_name) {
             return Ok(Value::Float(ordered_float::OrderedFloat(value)));
         }
+        if let Ok(value) = row.try_get::<rust_decimal::Decimal, _>(column_name) {
+            return Ok(Value::Decimal(value));
+        }
+        if let Ok(value) = row.try_get::<uuid::Uuid, _>(column_name) {
+            return Ok(Value::Uuid(value));
+        }
         if let Ok(value) = row.try_get::<bool, _>(column_name) {
             return Ok(Value::Bool(value));
         }
@@ -79,3 +231,42 @@ impl super::RowMapper<Postgres> for PostgresMapper {
         Ok(Value::Null)
     }
 }
+
+fn map_array(row: &sqlx::postgres::PgRow, column_name: &str) -> Value {
+    array::<i64>(row, column_name)
+        .or_else(|| array::<i32>(row, column_name))
+        .or_else(|| array::<f64>(row, column_name))
+        .or_else(|| array::<bool>(row, column_name))
+        .or_else(|| array::<uuid::Uuid>(row, column_name))
+        .or_else(|| array::<chrono::NaiveDate>(row, column_name))
+        .or_else(|| array::<chrono::NaiveTime>(row, column_name))
+        .or_else(|| array::<chrono::DateTime<chrono::Utc>>(row, column_name))
+        .or_else(|| array::<chrono::NaiveDateTime>(row, column_name))
+        .or_else(|| array::<String>(row, column_name))
+        // Arrays of enums decode as text, which the type check would refuse
+        .or_else(|| {
+            sqlx::Row::try_get_unchecked::<Vec<Option<String>>, _>(row, column_name)
+                .ok()
+                .map(array_value)
+        })
+        .unwrap_or(Value::Null)
+}
+
+fn array<'r, T>(row: &'r sqlx::postgres::PgRow, column_name: &str) -> Option<Value>
+where
+    Vec<Option<T>>: sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres>,
+    T: Into<Value>,
+{
+    sqlx::Row::try_get::<Vec<Option<T>>, _>(row, column_name)
+        .ok()
+        .map(array_value)
+}
+
+fn array_value<T: Into<Value>>(items: Vec<Option<T>>) -> Value {
+    Value::Array(
+        items
+            .into_iter()
+            .map(|item| item.map_or(Value::Null, Into::into))
+            .collect(),
+    )
+}
@@ -15,6 +15,7 @@ impl SqliteExecutor {
             name: "SQLite".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             driver_type: DriverType::SQLite,
+            max_connections: pool.options().get_max_connections(),
         };
         Self {
             pool,
@@ -25,6 +26,12 @@ impl SqliteExecutor {
     }
 }
 
+impl super::AffectedRows for sqlx::sqlite::SqliteQueryResult {
+    fn affected_rows(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
 impl<'q> super::ParameterBinder<'q, Sqlite> for SqliteBinder {
     fn bind_param(
         query: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
@@ -40,14 +47,28 @@ impl<'q> super::ParameterBinder<'q, Sqlite> for SqliteBinder {
             Value::Json(j) => query.bind(j.to_string()),
             Value::Binary(b) => query.bind(b),
             Value::NaiveDateTime(ndt) => query.bind(ndt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            // Text keeps decimals exact, where a REAL column would round them
+            Value::Decimal(d) => query.bind(d.to_string()),
+            Value::Uuid(u) => query.bind(u.to_string()),
+            Value::Date(d) => query.bind(d.format("%Y-%m-%d").to_string()),
+            Value::Time(t) => query.bind(t.format("%H:%M:%S%.f").to_string()),
+            Value::Interval(i) => query.bind(i.to_string()),
+            Value::Array(_) => query.bind(param.array_json().to_string()),
         }
     }
 }
 
 impl super::RowMapper<Sqlite> for SqliteMapper {
     fn map_value(row: &sqlx::sqlite::SqliteRow, column_name: &str) -> TikalResult<Value> {
-        use sqlx::Row;
+        use sqlx::{Row, ValueRef};
 
+        // Untyped expression columns (e.g. pragma results) would otherwise decode NULL as 0
+        if row
+            .try_get_raw(column_name)
+            .is_ok_and(|value| value.is_null())
+        {
+            return Ok(Value::Null);
+        }
         if let Ok(value) = row.try_get::<i64, _>(column_name) {
             return Ok(Value::Int(value));
         }
@@ -57,9 +78,6 @@ impl super::RowMapper<Sqlite> for SqliteMapper {
         if let Ok(value) = row.try_get::<bool, _>(column_name) {
             return Ok(Value::Bool(value));
         }
-        if let Ok(value) = row.try_get::<Vec<u8>, _>(column_name) {
-            return Ok(Value::Binary(value));
-        }
         if let Ok(value) = row.try_get::<String, _>(column_name) {
             if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&value) {
                 return Ok(Value::DateTime(dt.with_timezone(&chrono::Utc)));
@@ -74,6 +92,9 @@ impl super::RowMapper<Sqlite> for SqliteMapper {
             }
             return Ok(Value::Text(value));
         }
+        if let Ok(value) = row.try_get::<Vec<u8>, _>(column_name) {
+            return Ok(Value::Binary(value));
+        }
 
         Ok(Value::Null)
     }
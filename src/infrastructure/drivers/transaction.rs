@@ -1,4 +1,4 @@
-use crate::domain::repositories::executor::Transaction as DomainTransaction;
+use crate::domain::repositories::executor::{Savepoint, Transaction as DomainTransaction};
 use crate::domain::value_objects::Value;
 use crate::domain::TikalResult;
 use async_trait::async_trait;
@@ -30,12 +30,26 @@ where
     }
 }
 
+impl<DB, B, M> Drop for DatabaseTransaction<DB, B, M>
+where
+    DB: sqlx::Database,
+    B: for<'q> super::ParameterBinder<'q, DB> + Send + Sync,
+    M: super::RowMapper<DB> + Send + Sync,
+{
+    fn drop(&mut self) {
+        if self.tx.is_some() {
+            tracing::warn!("Transaction dropped without commit or rollback, rolling back");
+        }
+    }
+}
+
 #[async_trait]
 impl<DB, B, M> DomainTransaction for DatabaseTransaction<DB, B, M>
 where
     DB: sqlx::Database,
     B: for<'q> super::ParameterBinder<'q, DB> + Send + Sync,
     M: super::RowMapper<DB> + Send + Sync,
+    DB::QueryResult: super::AffectedRows,
     for<'r> &'r str: sqlx::ColumnIndex<DB::Row>,
     for<'q> <DB as sqlx::Database>::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
     for<'c> &'c mut <DB as sqlx::Database>::Connection: sqlx::Executor<'c, Database = DB>,
@@ -94,8 +108,8 @@ where
 
         let query = sqlx::query(sql);
         let query = B::bind_params(query, params);
-        query.execute(&mut **tx).await?;
-        Ok(1)
+        let result = query.execute(&mut **tx).await?;
+        Ok(super::AffectedRows::affected_rows(&result))
     }
 
     async fn commit(mut self: Box<Self>) -> TikalResult<()> {
@@ -121,4 +135,8 @@ where
         tx.rollback().await?;
         Ok(())
     }
+
+    async fn begin<'a>(&'a mut self) -> TikalResult<Box<dyn DomainTransaction + 'a>> {
+        Ok(Box::new(Savepoint::begin(self).await?))
+    }
 }
@@ -0,0 +1,179 @@
+use crate::domain::repositories::executor::QueryExecutor as DomainQueryExecutor;
+use crate::domain::repositories::executor::{Savepoint, Transaction as DomainTransaction};
+use crate::domain::repositories::types::DriverInfo;
+use crate::domain::value_objects::Value;
+use crate::domain::TikalResult;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+type SharedTransaction = Arc<Mutex<Option<Box<dyn DomainTransaction>>>>;
+
+#[derive(Clone)]
+pub struct TransactionExecutor {
+    tx: SharedTransaction,
+    driver_info: DriverInfo,
+    savepoints: Arc<AtomicUsize>,
+    savepoint: Option<String>,
+}
+
+impl TransactionExecutor {
+    pub fn new(tx: Box<dyn DomainTransaction>, driver_info: DriverInfo) -> Self {
+        Self {
+            tx: Arc::new(Mutex::new(Some(tx))),
+            driver_info,
+            savepoints: Arc::new(AtomicUsize::new(0)),
+            savepoint: None,
+        }
+    }
+
+    pub async fn begin(&self) -> TikalResult<Self> {
+        let id = self.savepoints.fetch_add(1, Ordering::SeqCst) + 1;
+        let name = format!("tikal_nested_{}", id);
+        self.savepoint(&name).await?;
+        Ok(Self {
+            savepoint: Some(name),
+            ..self.clone()
+        })
+    }
+
+    pub fn is_nested(&self) -> bool {
+        self.savepoint.is_some()
+    }
+
+    pub async fn commit(&self) -> TikalResult<()> {
+        if let Some(name) = &self.savepoint {
+            return self.release(name).await;
+        }
+        self.take("Cannot commit consumed transaction")
+            .await?
+            .commit()
+            .await
+    }
+
+    pub async fn rollback(&self) -> TikalResult<()> {
+        if let Some(name) = &self.savepoint {
+            self.rollback_to(name).await?;
+            return self.release(name).await;
+        }
+        self.take("Cannot rollback consumed transaction")
+            .await?
+            .rollback()
+            .await
+    }
+
+    pub async fn savepoint(&self, name: &str) -> TikalResult<()> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard
+            .as_mut()
+            .ok_or_else(|| consumed_error("Cannot create savepoint on consumed transaction"))?;
+        tx.savepoint(name).await
+    }
+
+    pub async fn rollback_to(&self, name: &str) -> TikalResult<()> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard
+            .as_mut()
+            .ok_or_else(|| consumed_error("Cannot rollback savepoint on consumed transaction"))?;
+        tx.rollback_to(name).await
+    }
+
+    pub async fn release(&self, name: &str) -> TikalResult<()> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard
+            .as_mut()
+            .ok_or_else(|| consumed_error("Cannot release savepoint on consumed transaction"))?;
+        tx.release(name).await
+    }
+
+    pub async fn is_active(&self) -> bool {
+        self.tx.lock().await.is_some()
+    }
+
+    async fn take(&self, context: &str) -> TikalResult<Box<dyn DomainTransaction>> {
+        self.tx
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| consumed_error(context))
+    }
+}
+
+fn consumed_error(context: &str) -> crate::domain::TikalError {
+    crate::domain::TikalError::database_error("Transaction already consumed", context, None)
+}
+
+#[async_trait]
+impl DomainQueryExecutor for TransactionExecutor {
+    async fn fetch_all(
+        &self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> TikalResult<Vec<HashMap<String, Value>>> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard
+            .as_mut()
+            .ok_or_else(|| consumed_error("Cannot execute query on consumed transaction"))?;
+        tx.fetch_all(sql, params).await
+    }
+
+    async fn execute(&self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard
+            .as_mut()
+            .ok_or_else(|| consumed_error("Cannot execute query on consumed transaction"))?;
+        tx.execute(sql, params).await
+    }
+
+    async fn execute_with_rows(&self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
+        self.execute(sql, params).await
+    }
+
+    async fn begin_transaction(&self) -> TikalResult<Box<dyn DomainTransaction>> {
+        Ok(Box::new(NestedTransaction {
+            executor: self.begin().await?,
+        }))
+    }
+
+    async fn ping(&self) -> TikalResult<bool> {
+        self.fetch_all("SELECT 1", Vec::new()).await?;
+        Ok(true)
+    }
+
+    fn driver_info(&self) -> DriverInfo {
+        self.driver_info.clone()
+    }
+}
+
+struct NestedTransaction {
+    executor: TransactionExecutor,
+}
+
+#[async_trait]
+impl DomainTransaction for NestedTransaction {
+    async fn fetch_all(
+        &mut self,
+        sql: &str,
+        params: Vec<Value>,
+    ) -> TikalResult<Vec<HashMap<String, Value>>> {
+        self.executor.fetch_all(sql, params).await
+    }
+
+    async fn execute(&mut self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
+        self.executor.execute(sql, params).await
+    }
+
+    async fn commit(self: Box<Self>) -> TikalResult<()> {
+        self.executor.commit().await
+    }
+
+    async fn rollback(self: Box<Self>) -> TikalResult<()> {
+        self.executor.rollback().await
+    }
+
+    async fn begin<'a>(&'a mut self) -> TikalResult<Box<dyn DomainTransaction + 'a>> {
+        Ok(Box::new(Savepoint::begin(self).await?))
+    }
+}
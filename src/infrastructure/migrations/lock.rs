@@ -0,0 +1,223 @@
+use crate::domain::repositories::types::DriverType;
+use crate::domain::value_objects::Value;
+use crate::domain::{TikalError, TikalResult};
+use crate::infrastructure::drivers::traits::QueryExecutor;
+use crate::infrastructure::drivers::TransactionExecutor;
+use std::time::{Duration, Instant};
+
+const LOCK_NAME: &str = "__migrations";
+// Advisory locks are scoped to the database, so a fixed key is enough
+const POSTGRES_LOCK_KEY: i64 = 0x7469_6b61;
+const SQLITE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct MigrationLock<'a> {
+    executor: &'a dyn QueryExecutor,
+    // PostgreSQL and MySQL locks belong to a connection, which this transaction
+    // keeps checked out of the pool until the lock is released
+    session: Option<TransactionExecutor>,
+}
+
+impl<'a> MigrationLock<'a> {
+    pub async fn acquire(executor: &'a dyn QueryExecutor, timeout: Duration) -> TikalResult<Self> {
+        let driver = executor.driver_info();
+        // Each migration checks out a connection of its own while the session
+        // holds one, so a single-connection pool would wait on itself
+        if driver.driver_type != DriverType::SQLite && driver.max_connections < 2 {
+            return Err(TikalError::config(&format!(
+                "Migrating {} needs a pool of at least 2 connections, one holds the \
+                 migration lock (the pool allows {}, raise DATABASE_MAX_CONNECTIONS)",
+                driver.name, driver.max_connections
+            )));
+        }
+
+        let session = match driver.driver_type {
+            DriverType::PostgreSQL => Some(acquire_postgres(executor, timeout).await?),
+            DriverType::MySQL => Some(acquire_mysql(executor, timeout).await?),
+            DriverType::SQLite => {
+                acquire_sqlite(executor, timeout).await?;
+                None
+            }
+        };
+        Ok(Self { executor, session })
+    }
+
+    pub async fn release(self) -> TikalResult<()> {
+        let Some(session) = self.session else {
+            return self
+                .executor
+                .execute(SQLITE_RELEASE, vec![])
+                .await
+                .map(|_| ());
+        };
+        match self.executor.driver_info().driver_type {
+            DriverType::MySQL => {
+                session.fetch_all(MYSQL_RELEASE, vec![]).await?;
+                session.commit().await
+            }
+            // pg_advisory_xact_lock is released when its transaction ends
+            _ => session.rollback().await,
+        }
+    }
+
+    // A SQLite lock outlives a process that dies while migrating; the other
+    // drivers release it with the connection, so there is nothing to clear.
+    pub async fn force_release(executor: &dyn QueryExecutor) -> TikalResult<()> {
+        if executor.driver_info().driver_type == DriverType::SQLite {
+            executor.execute(SQLITE_CREATE, vec![]).await?;
+            executor.execute(SQLITE_RELEASE, vec![]).await?;
+        }
+        Ok(())
+    }
+}
+
+const MYSQL_RELEASE: &str = "SELECT RELEASE_LOCK(CONCAT('__migrations.', DATABASE())) AS released";
+const SQLITE_CREATE: &str = "CREATE TABLE IF NOT EXISTS __migrations_lock \
+                             (id INTEGER PRIMARY KEY, holder TEXT NOT NULL, locked_at TEXT NOT NULL)";
+const SQLITE_RELEASE: &str = "DELETE FROM __migrations_lock WHERE id = 1";
+
+async fn begin_session(executor: &dyn QueryExecutor) -> TikalResult<TransactionExecutor> {
+    let tx = executor.begin_transaction().await?;
+    Ok(TransactionExecutor::new(tx, executor.driver_info()))
+}
+
+async fn abandon_session(session: TransactionExecutor) {
+    if let Err(e) = session.rollback().await {
+        tracing::warn!("Failed to close migration lock session: {}", e);
+    }
+}
+
+async fn acquire_postgres(
+    executor: &dyn QueryExecutor,
+    timeout: Duration,
+) -> TikalResult<TransactionExecutor> {
+    let session = begin_session(executor).await?;
+    // A lock_timeout of 0 would mean waiting forever
+    let timeout_ms = timeout.as_millis().max(1);
+    let result = async {
+        session
+            .execute(&format!("SET LOCAL lock_timeout = {}", timeout_ms), vec![])
+            .await?;
+        session
+            .execute(
+                "SELECT pg_advisory_xact_lock($1)",
+                vec![Value::Int(POSTGRES_LOCK_KEY)],
+            )
+            .await
+    }
+    .await;
+
+    match result {
+        Ok(_) => Ok(session),
+        Err(e) => {
+            abandon_session(session).await;
+            let timed_out = matches!(
+                &e,
+                TikalError::DatabaseError { error_code: Some(code), .. } if code == "55P03"
+            );
+            if !timed_out {
+                return Err(e);
+            }
+            let holder = executor
+                .fetch_all(
+                    "SELECT pid::text AS holder FROM pg_locks \
+                     WHERE locktype = 'advisory' AND granted \
+                     AND objid::bigint = $1 AND objsubid = 1",
+                    vec![Value::Int(POSTGRES_LOCK_KEY)],
+                )
+                .await
+                .ok()
+                .and_then(|rows| holder(&rows).map(|pid| format!("backend pid {}", pid)));
+            Err(timed_out_error(holder, timeout))
+        }
+    }
+}
+
+async fn acquire_mysql(
+    executor: &dyn QueryExecutor,
+    timeout: Duration,
+) -> TikalResult<TransactionExecutor> {
+    let session = begin_session(executor).await?;
+    // GET_LOCK takes whole seconds, and a negative timeout waits forever
+    let timeout_secs = timeout.as_secs_f64().ceil() as i64;
+    let result = session
+        .fetch_all(
+            "SELECT GET_LOCK(CONCAT('__migrations.', DATABASE()), ?) AS acquired",
+            vec![Value::Int(timeout_secs)],
+        )
+        .await;
+
+    let acquired = match result {
+        Ok(rows) => rows
+            .first()
+            .and_then(|row| row.get("acquired"))
+            .is_some_and(|value| matches!(value, Value::Int(1))),
+        Err(e) => {
+            abandon_session(session).await;
+            return Err(e);
+        }
+    };
+    if acquired {
+        return Ok(session);
+    }
+
+    abandon_session(session).await;
+    let holder = executor
+        .fetch_all(
+            "SELECT IS_USED_LOCK(CONCAT('__migrations.', DATABASE())) AS holder",
+            vec![],
+        )
+        .await
+        .ok()
+        .and_then(|rows| holder(&rows).map(|id| format!("connection {}", id)));
+    Err(timed_out_error(holder, timeout))
+}
+
+async fn acquire_sqlite(executor: &dyn QueryExecutor, timeout: Duration) -> TikalResult<()> {
+    executor.execute(SQLITE_CREATE, vec![]).await?;
+
+    let started = Instant::now();
+    loop {
+        let inserted = executor
+            .execute(
+                "INSERT OR IGNORE INTO __migrations_lock (id, holder, locked_at) VALUES (1, ?, ?)",
+                vec![
+                    Value::Text(format!("pid {}", std::process::id())),
+                    Value::Text(chrono::Utc::now().to_rfc3339()),
+                ],
+            )
+            .await?;
+        if inserted > 0 {
+            return Ok(());
+        }
+
+        if started.elapsed() >= timeout {
+            let holder = executor
+                .fetch_all(
+                    "SELECT holder || ' since ' || locked_at AS holder \
+                     FROM __migrations_lock WHERE id = 1",
+                    vec![],
+                )
+                .await
+                .ok()
+                .and_then(|rows| holder(&rows));
+            return Err(timed_out_error(holder, timeout));
+        }
+        tokio::time::sleep(SQLITE_POLL_INTERVAL).await;
+    }
+}
+
+fn holder(rows: &[std::collections::HashMap<String, Value>]) -> Option<String> {
+    match rows.first()?.get("holder")? {
+        Value::Text(holder) => Some(holder.clone()),
+        Value::Int(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+fn timed_out_error(holder: Option<String>, timeout: Duration) -> TikalError {
+    let context = match &holder {
+        Some(holder) => format!("held by {}, gave up after {:?}", holder, timeout),
+        None => format!("gave up after {:?}", timeout),
+    };
+    TikalError::migration_lock_failed(LOCK_NAME, holder).with_context(context)
+}
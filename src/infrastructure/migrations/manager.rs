@@ -1,5 +1,6 @@
 use crate::domain::TikalResult;
 use crate::infrastructure::drivers::traits::QueryExecutor;
+use crate::infrastructure::migrations::AppliedMigration;
 use crate::infrastructure::query_builder::generators::SqlGenerator;
 use crate::infrastructure::schema::builders::TableBuilder;
 use crate::infrastructure::schema::ColumnType;
@@ -24,10 +25,29 @@ impl<'a, G: SqlGenerator> MigrationManager<'a, G> {
             .finish()
             .column("version", ColumnType::BigInt)
             .finish()
+            .column("applied_at", ColumnType::DateTime)
+            .nullable()
+            .finish()
+            .column("checksum", ColumnType::Text)
+            .nullable()
+            .finish()
             .build();
 
         let sql = self.generator.generate_create_table(&table);
         self.executor.execute(&sql, vec![]).await?;
+
+        // Tables created by older versions lack the nullable bookkeeping columns.
+        // The probe is unquoted, SQLite treats an unknown quoted name as a string.
+        for column in table.columns.iter().filter(|c| c.nullable) {
+            let probe = format!("SELECT {} FROM __migrations WHERE 1 = 0", column.name);
+            if self.executor.fetch_all(&probe, vec![]).await.is_err() {
+                let sql = format!(
+                    "ALTER TABLE __migrations ADD COLUMN {}",
+                    self.generator.generate_column_definition(column)
+                );
+                self.executor.execute(&sql, vec![]).await?;
+            }
+        }
         Ok(())
     }
 
@@ -67,12 +87,102 @@ impl<'a, G: SqlGenerator> MigrationManager<'a, G> {
         Ok(applied)
     }
 
-    pub async fn mark_migration_applied(&self, name: &str, version: u64) -> TikalResult<()> {
+    pub async fn get_applied_records(&self) -> TikalResult<Vec<AppliedMigration>> {
+        let sql = "SELECT name, version, applied_at, checksum FROM __migrations ORDER BY version ASC, id ASC";
+        let rows = self.executor.fetch_all(sql, vec![]).await?;
+        let mut records = Vec::new();
+        for row in rows {
+            let name = match row.get("name") {
+                Some(crate::domain::value_objects::Value::Text(s)) => s.clone(),
+                _ => {
+                    return Err(crate::domain::TikalError::mapping(
+                        "migration",
+                        "name is not text",
+                    ))
+                }
+            };
+            let version = match row.get("version") {
+                Some(crate::domain::value_objects::Value::Int(i)) => *i as u64,
+                _ => {
+                    return Err(crate::domain::TikalError::mapping(
+                        "migration",
+                        "version is not int",
+                    ))
+                }
+            };
+            let applied_at = match row.get("applied_at") {
+                Some(crate::domain::value_objects::Value::DateTime(dt)) => Some(*dt),
+                Some(crate::domain::value_objects::Value::NaiveDateTime(ndt)) => {
+                    Some(ndt.and_utc())
+                }
+                _ => None,
+            };
+            let checksum = match row.get("checksum") {
+                Some(crate::domain::value_objects::Value::Text(s)) => Some(s.clone()),
+                _ => None,
+            };
+            records.push(AppliedMigration {
+                name,
+                version,
+                applied_at,
+                checksum,
+            });
+        }
+        Ok(records)
+    }
+
+    pub async fn mark_migration_applied(
+        &self,
+        name: &str,
+        version: u64,
+        checksum: Option<&str>,
+    ) -> TikalResult<()> {
+        let sql = format!(
+            "INSERT INTO __migrations (name, version, applied_at, checksum) VALUES ({}, {}, {}, {})",
+            self.generator.placeholder(0),
+            self.generator.placeholder(1),
+            self.generator.placeholder(2),
+            self.generator.placeholder(3)
+        );
+        let params = vec![
+            crate::domain::value_objects::Value::Text(name.to_string()),
+            crate::domain::value_objects::Value::Int(version as i64),
+            crate::domain::value_objects::Value::DateTime(chrono::Utc::now()),
+            checksum
+                .map(|c| crate::domain::value_objects::Value::Text(c.to_string()))
+                .unwrap_or(crate::domain::value_objects::Value::Null),
+        ];
+        self.executor.execute(&sql, params).await?;
+        Ok(())
+    }
+
+    pub async fn update_checksum(
+        &self,
+        name: &str,
+        version: u64,
+        checksum: &str,
+    ) -> TikalResult<()> {
         let sql = format!(
-            "INSERT INTO __migrations (name, version) VALUES ({}, {})",
+            "UPDATE __migrations SET checksum = {} WHERE name = {} AND version = {}",
+            self.generator.placeholder(0),
             self.generator.placeholder(1),
             self.generator.placeholder(2)
         );
+        let params = vec![
+            crate::domain::value_objects::Value::Text(checksum.to_string()),
+            crate::domain::value_objects::Value::Text(name.to_string()),
+            crate::domain::value_objects::Value::Int(version as i64),
+        ];
+        self.executor.execute(&sql, params).await?;
+        Ok(())
+    }
+
+    pub async fn mark_migration_reverted(&self, name: &str, version: u64) -> TikalResult<()> {
+        let sql = format!(
+            "DELETE FROM __migrations WHERE name = {} AND version = {}",
+            self.generator.placeholder(0),
+            self.generator.placeholder(1)
+        );
         let params = vec![
             crate::domain::value_objects::Value::Text(name.to_string()),
             crate::domain::value_objects::Value::Int(version as i64),
@@ -84,8 +194,8 @@ impl<'a, G: SqlGenerator> MigrationManager<'a, G> {
     pub async fn is_migration_applied(&self, name: &str, version: u64) -> TikalResult<bool> {
         let sql = format!(
             "SELECT COUNT(*) as count FROM __migrations WHERE name = {} AND version = {}",
-            self.generator.placeholder(1),
-            self.generator.placeholder(2)
+            self.generator.placeholder(0),
+            self.generator.placeholder(1)
         );
         let params = vec![
             crate::domain::value_objects::Value::Text(name.to_string()),
@@ -8,7 +8,50 @@ pub trait Migration: Send + Sync {
     async fn down(&self, executor: &dyn QueryExecutor) -> TikalResult<()>;
     fn name(&self) -> &str;
     fn version(&self) -> u64;
+
+    fn checksum(&self) -> Option<String> {
+        None
+    }
+}
+
+pub fn checksum(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub name: String,
+    pub version: u64,
+    pub applied_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub checksum: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    Missing,
+    Modified,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DriftPolicy {
+    #[default]
+    Error,
+    Warn,
+    Ignore,
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub name: String,
+    pub version: u64,
+    pub state: MigrationState,
+    pub applied_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+pub mod lock;
 pub mod manager;
 pub mod runner;
+pub mod sql;
@@ -1,13 +1,30 @@
 use crate::domain::TikalResult;
 use crate::infrastructure::drivers::traits::QueryExecutor;
+use crate::infrastructure::drivers::TransactionExecutor;
+use crate::infrastructure::migrations::lock::MigrationLock;
 use crate::infrastructure::migrations::manager::MigrationManager;
-use crate::infrastructure::migrations::Migration;
+use crate::infrastructure::migrations::{
+    AppliedMigration, DriftPolicy, Migration, MigrationState, MigrationStatus,
+};
 use crate::infrastructure::query_builder::generators::SqlGenerator;
+use std::future::Future;
+use std::time::Duration;
+
+const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+}
 
 pub struct MigrationRunner<'a, G: SqlGenerator> {
     executor: &'a dyn QueryExecutor,
+    generator: &'a G,
     manager: MigrationManager<'a, G>,
     migrations: Vec<Box<dyn Migration + 'a>>,
+    drift_policy: DriftPolicy,
+    lock_timeout: Duration,
 }
 
 impl<'a, G: SqlGenerator> MigrationRunner<'a, G> {
@@ -19,14 +36,154 @@ impl<'a, G: SqlGenerator> MigrationRunner<'a, G> {
         let manager = MigrationManager::new(executor, generator);
         Self {
             executor,
+            generator,
             manager,
             migrations,
+            drift_policy: DriftPolicy::default(),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
         }
     }
 
+    pub fn with_drift_policy(mut self, policy: DriftPolicy) -> Self {
+        self.drift_policy = policy;
+        self
+    }
+
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
     pub async fn run_pending_migrations(&self) -> TikalResult<()> {
+        self.locked(self.apply_pending()).await
+    }
+
+    // Returns the migrations that were rolled back, latest first
+    pub async fn rollback(&self, steps: usize) -> TikalResult<Vec<AppliedMigration>> {
+        self.locked(self.revert_latest(steps)).await
+    }
+
+    pub async fn rollback_to(&self, version: u64) -> TikalResult<Vec<AppliedMigration>> {
+        self.locked(self.revert_after(version)).await
+    }
+
+    pub async fn reset(&self) -> TikalResult<Vec<AppliedMigration>> {
+        self.rollback(usize::MAX).await
+    }
+
+    pub async fn refresh(&self) -> TikalResult<()> {
+        self.locked(async {
+            self.revert_latest(usize::MAX).await?;
+            self.apply_pending().await
+        })
+        .await
+    }
+
+    // Clears a SQLite lock left behind by a process that died while migrating
+    pub async fn force_unlock(&self) -> TikalResult<()> {
+        MigrationLock::force_release(self.executor).await
+    }
+
+    pub async fn status(&self) -> TikalResult<Vec<MigrationStatus>> {
         self.manager.create_migrations_table().await?;
 
+        let applied = self.manager.get_applied_records().await?;
+        let mut statuses: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let record = applied
+                    .iter()
+                    .find(|r| r.name == migration.name() && r.version == migration.version());
+                MigrationStatus {
+                    name: migration.name().to_string(),
+                    version: migration.version(),
+                    state: match record {
+                        Some(record) if Self::is_modified(migration.as_ref(), record) => {
+                            MigrationState::Modified
+                        }
+                        Some(_) => MigrationState::Applied,
+                        None => MigrationState::Pending,
+                    },
+                    applied_at: record.and_then(|r| r.applied_at),
+                }
+            })
+            .collect();
+
+        for record in &applied {
+            if self.find_migration(record).is_none() {
+                statuses.push(MigrationStatus {
+                    name: record.name.clone(),
+                    version: record.version,
+                    state: MigrationState::Missing,
+                    applied_at: record.applied_at,
+                });
+            }
+        }
+
+        statuses.sort_by(|a, b| a.version.cmp(&b.version).then(a.name.cmp(&b.name)));
+        Ok(statuses)
+    }
+
+    pub async fn check_drift(&self) -> TikalResult<()> {
+        if self.drift_policy == DriftPolicy::Ignore {
+            return Ok(());
+        }
+
+        let mut modified = Vec::new();
+        for record in self.manager.get_applied_records().await? {
+            let Some(migration) = self.find_migration(&record) else {
+                continue;
+            };
+            match (migration.checksum(), &record.checksum) {
+                (Some(checksum), None) => {
+                    self.manager
+                        .update_checksum(&record.name, record.version, &checksum)
+                        .await?;
+                }
+                _ if Self::is_modified(migration, &record) => {
+                    modified.push(format!("{} ({})", record.name, record.version));
+                }
+                _ => {}
+            }
+        }
+
+        if modified.is_empty() {
+            return Ok(());
+        }
+
+        let message = format!(
+            "Applied migrations were modified after they ran: {}",
+            modified.join(", ")
+        );
+        match self.drift_policy {
+            DriftPolicy::Error => Err(crate::domain::TikalError::invalid_state(&message)),
+            _ => {
+                tracing::warn!("{}", message);
+                Ok(())
+            }
+        }
+    }
+
+    // Everything that reads or writes __migrations runs under the lock, so a
+    // runner that had to wait sees what the previous holder applied.
+    async fn locked<T>(&self, operation: impl Future<Output = TikalResult<T>>) -> TikalResult<T> {
+        let lock = MigrationLock::acquire(self.executor, self.lock_timeout).await?;
+        let result = operation.await;
+        match lock.release().await {
+            Ok(()) => result,
+            Err(e) if result.is_ok() => Err(e),
+            Err(e) => {
+                tracing::warn!("Failed to release migration lock: {}", e);
+                result
+            }
+        }
+    }
+
+    async fn apply_pending(&self) -> TikalResult<()> {
+        self.manager.create_migrations_table().await?;
+        self.check_drift().await?;
+
         let applied = self.manager.get_applied_migrations().await?;
 
         let mut sorted_migrations: Vec<_> = self.migrations.iter().collect();
@@ -42,11 +199,137 @@ impl<'a, G: SqlGenerator> MigrationRunner<'a, G> {
                 }
             }
 
-            migration.up(self.executor).await?;
+            self.run_migration(migration.as_ref(), Direction::Up)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn revert_latest(&self, steps: usize) -> TikalResult<Vec<AppliedMigration>> {
+        self.manager.create_migrations_table().await?;
+        self.check_drift().await?;
+
+        let applied = self.manager.get_applied_records().await?;
+        let mut reverted = Vec::new();
+        for record in applied.into_iter().rev().take(steps) {
+            self.revert(&record).await?;
+            reverted.push(record);
+        }
+
+        Ok(reverted)
+    }
 
-            self.manager.mark_migration_applied(name, version).await?;
+    async fn revert_after(&self, version: u64) -> TikalResult<Vec<AppliedMigration>> {
+        self.manager.create_migrations_table().await?;
+        self.check_drift().await?;
+
+        let applied = self.manager.get_applied_records().await?;
+        let mut reverted = Vec::new();
+        for record in applied.into_iter().rev().filter(|r| r.version > version) {
+            self.revert(&record).await?;
+            reverted.push(record);
         }
 
+        Ok(reverted)
+    }
+
+    fn is_modified(migration: &(dyn Migration + 'a), record: &AppliedMigration) -> bool {
+        match (migration.checksum(), &record.checksum) {
+            (Some(current), Some(applied)) => &current != applied,
+            _ => false,
+        }
+    }
+
+    fn find_migration(&self, record: &AppliedMigration) -> Option<&(dyn Migration + 'a)> {
+        self.migrations
+            .iter()
+            .find(|m| m.name() == record.name && m.version() == record.version)
+            .map(|m| m.as_ref())
+    }
+
+    async fn revert(&self, record: &AppliedMigration) -> TikalResult<()> {
+        let migration = self.find_migration(record).ok_or_else(|| {
+            crate::domain::TikalError::invalid_state(&format!(
+                "Migration '{}' (version {}) is applied but not registered, cannot roll it back",
+                record.name, record.version
+            ))
+        })?;
+        self.run_migration(migration, Direction::Down).await
+    }
+
+    async fn run_migration(
+        &self,
+        migration: &(dyn Migration + 'a),
+        direction: Direction,
+    ) -> TikalResult<()> {
+        let name = migration.name();
+        let version = migration.version();
+        let context = || match direction {
+            Direction::Up => format!("Migration '{}' failed", name),
+            Direction::Down => format!("Rollback of migration '{}' failed", name),
+        };
+
+        let result = if self.generator.supports_transactional_ddl() {
+            self.apply_in_transaction(migration, direction).await
+        } else {
+            self.apply(self.executor, migration, direction).await
+        };
+        result.map_err(|e| e.with_context(context()))?;
+
+        match direction {
+            Direction::Up => tracing::info!("Applied migration {} ({})", name, version),
+            Direction::Down => tracing::info!("Rolled back migration {} ({})", name, version),
+        }
         Ok(())
     }
+
+    async fn apply_in_transaction(
+        &self,
+        migration: &(dyn Migration + 'a),
+        direction: Direction,
+    ) -> TikalResult<()> {
+        let tx = self.executor.begin_transaction().await?;
+        let tx = TransactionExecutor::new(tx, self.executor.driver_info());
+        match self.apply(&tx, migration, direction).await {
+            Ok(()) => tx.commit().await,
+            Err(e) => {
+                if let Err(rollback_err) = tx.rollback().await {
+                    tracing::warn!(
+                        "Failed to roll back migration '{}': {}",
+                        migration.name(),
+                        rollback_err
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn apply(
+        &self,
+        executor: &dyn QueryExecutor,
+        migration: &(dyn Migration + 'a),
+        direction: Direction,
+    ) -> TikalResult<()> {
+        let manager = MigrationManager::new(executor, self.generator);
+        match direction {
+            Direction::Up => {
+                migration.up(executor).await?;
+                manager
+                    .mark_migration_applied(
+                        migration.name(),
+                        migration.version(),
+                        migration.checksum().as_deref(),
+                    )
+                    .await
+            }
+            Direction::Down => {
+                migration.down(executor).await?;
+                manager
+                    .mark_migration_reverted(migration.name(), migration.version())
+                    .await
+            }
+        }
+    }
 }
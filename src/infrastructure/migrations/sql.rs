@@ -0,0 +1,419 @@
+use crate::domain::repositories::types::DriverType;
+use crate::domain::TikalResult;
+use crate::infrastructure::drivers::traits::QueryExecutor;
+use crate::infrastructure::migrations::Migration;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub struct SqlMigration {
+    version: u64,
+    name: String,
+    up: String,
+    down: Option<String>,
+}
+
+impl SqlMigration {
+    pub fn new(
+        version: u64,
+        name: impl Into<String>,
+        up: impl Into<String>,
+        down: Option<String>,
+    ) -> Self {
+        Self {
+            version,
+            name: name.into(),
+            up: up.into(),
+            down,
+        }
+    }
+
+    async fn run_script(executor: &dyn QueryExecutor, script: &str) -> TikalResult<()> {
+        for statement in split_statements(script, &executor.driver_info().driver_type) {
+            executor.execute(&statement, vec![]).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Migration for SqlMigration {
+    async fn up(&self, executor: &dyn QueryExecutor) -> TikalResult<()> {
+        Self::run_script(executor, &self.up).await
+    }
+
+    async fn down(&self, executor: &dyn QueryExecutor) -> TikalResult<()> {
+        let down = self.down.as_deref().ok_or_else(|| {
+            crate::domain::TikalError::invalid_state(&format!(
+                "Migration '{}' has no down script",
+                self.name
+            ))
+        })?;
+        Self::run_script(executor, down).await
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(super::checksum(&self.up))
+    }
+}
+
+// Shared with embed_migrations! so both loaders accept the same file names
+pub use tikal_common::parse_file_name;
+
+pub fn load_migrations(dir: impl AsRef<Path>) -> TikalResult<Vec<Box<dyn Migration>>> {
+    let dir = dir.as_ref();
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        crate::domain::TikalError::config(&format!(
+            "Cannot read migrations directory '{}': {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    let mut scripts: BTreeMap<(u64, String), (Option<String>, Option<String>)> = BTreeMap::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| crate::domain::TikalError::config(&e.to_string()))?
+            .path();
+        let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        if !file_name.ends_with(".sql") {
+            continue;
+        }
+        let (version, name, is_up) = parse_file_name(file_name).ok_or_else(|| {
+            crate::domain::TikalError::config(&format!(
+                "Invalid migration file name '{}', expected <version>_<name>.up.sql or .down.sql",
+                file_name
+            ))
+        })?;
+
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            crate::domain::TikalError::config(&format!(
+                "Cannot read migration '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let slot = scripts.entry((version, name)).or_default();
+        if is_up {
+            slot.0 = Some(content);
+        } else {
+            slot.1 = Some(content);
+        }
+    }
+
+    let mut migrations: Vec<Box<dyn Migration>> = Vec::new();
+    for ((version, name), (up, down)) in scripts {
+        let up = up.ok_or_else(|| {
+            crate::domain::TikalError::config(&format!(
+                "Migration '{}_{}' has a down script but no up script",
+                version, name
+            ))
+        })?;
+        migrations.push(Box::new(SqlMigration::new(version, name, up, down)));
+    }
+    Ok(migrations)
+}
+
+pub fn write_migration(
+    dir: impl AsRef<Path>,
+    name: &str,
+    up: &str,
+    down: Option<&str>,
+) -> TikalResult<PathBuf> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(crate::domain::TikalError::validation(
+            "name",
+            "Migration names may only contain letters, digits and underscores",
+        ));
+    }
+
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).map_err(|e| {
+        crate::domain::TikalError::config(&format!(
+            "Cannot create migrations directory '{}': {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    let version = chrono::Utc::now().format("%Y%m%d%H%M%S");
+    let up_path = dir.join(format!("{}_{}.up.sql", version, name));
+    std::fs::write(&up_path, up).map_err(|e| write_error(&up_path, e))?;
+    if let Some(down) = down {
+        let down_path = dir.join(format!("{}_{}.down.sql", version, name));
+        std::fs::write(&down_path, down).map_err(|e| write_error(&down_path, e))?;
+    }
+    Ok(up_path)
+}
+
+fn write_error(path: &Path, e: std::io::Error) -> crate::domain::TikalError {
+    crate::domain::TikalError::config(&format!(
+        "Cannot write migration '{}': {}",
+        path.display(),
+        e
+    ))
+}
+
+// Splits a script on `;`, skipping quoted text, comments and the bodies of
+// triggers and stored routines (BEGIN ... END), which contain `;` themselves
+pub fn split_statements(sql: &str, driver_type: &DriverType) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut has_code = false;
+    let mut leading_words: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '-' if next == Some('-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    current.push(chars[i]);
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if next == Some('*') => {
+                let end = find(&chars, i + 2, &['*', '/']).map_or(chars.len(), |e| e + 2);
+                current.extend(&chars[i..end]);
+                i = end;
+                continue;
+            }
+            '\'' | '"' | '`' => {
+                let end = closing_quote(&chars, i, backslash_escapes(&chars, i, driver_type));
+                current.extend(&chars[i..end]);
+                has_code = true;
+                i = end;
+                continue;
+            }
+            '$' => {
+                if let Some(tag_end) = dollar_tag(&chars, i) {
+                    let tag: Vec<char> = chars[i..=tag_end].to_vec();
+                    let end =
+                        find(&chars, tag_end + 1, &tag).map_or(chars.len(), |e| e + tag.len());
+                    current.extend(&chars[i..end]);
+                    has_code = true;
+                    i = end;
+                    continue;
+                }
+            }
+            ';' if depth == 0 => {
+                if has_code {
+                    statements.push(current.trim().to_string());
+                }
+                current.clear();
+                has_code = false;
+                leading_words.clear();
+                i += 1;
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let end = word_end(&chars, i);
+                let word: String = chars[i..end].iter().collect::<String>().to_uppercase();
+                current.extend(&chars[i..end]);
+                has_code = true;
+                i = end;
+
+                if leading_words.len() < 8 {
+                    leading_words.push(word.clone());
+                }
+                if is_routine(&leading_words) {
+                    match word.as_str() {
+                        "BEGIN" | "CASE" => depth += 1,
+                        // END IF, END LOOP and friends close blocks that were never counted
+                        "END" => {
+                            let next_word = next_word(&chars, i);
+                            if !matches!(
+                                next_word.as_deref(),
+                                Some("IF" | "LOOP" | "WHILE" | "REPEAT")
+                            ) {
+                                depth = depth.saturating_sub(1);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        if !c.is_whitespace() {
+            has_code = true;
+        }
+        current.push(c);
+        i += 1;
+    }
+
+    if has_code {
+        statements.push(current.trim().to_string());
+    }
+    statements
+}
+
+fn is_routine(leading_words: &[String]) -> bool {
+    leading_words.first().is_some_and(|w| w == "CREATE")
+        && leading_words
+            .iter()
+            .any(|w| matches!(w.as_str(), "TRIGGER" | "PROCEDURE" | "FUNCTION"))
+}
+
+fn word_end(chars: &[char], start: usize) -> usize {
+    (start..chars.len())
+        .find(|&i| !(chars[i].is_alphanumeric() || chars[i] == '_'))
+        .unwrap_or(chars.len())
+}
+
+fn next_word(chars: &[char], from: usize) -> Option<String> {
+    let start = (from..chars.len()).find(|&i| !chars[i].is_whitespace())?;
+    let end = word_end(chars, start);
+    (end > start).then(|| chars[start..end].iter().collect::<String>().to_uppercase())
+}
+
+fn find(chars: &[char], from: usize, needle: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&i| chars[i..].starts_with(needle))
+}
+
+// MySQL escapes with backslashes inside both string quotes, PostgreSQL only in E'' strings
+fn backslash_escapes(chars: &[char], start: usize, driver_type: &DriverType) -> bool {
+    match driver_type {
+        DriverType::MySQL => chars[start] != '`',
+        DriverType::PostgreSQL => {
+            chars[start] == '\''
+                && start > 0
+                && matches!(chars[start - 1], 'E' | 'e')
+                && (start < 2 || !(chars[start - 2].is_alphanumeric() || chars[start - 2] == '_'))
+        }
+        DriverType::SQLite => false,
+    }
+}
+
+fn closing_quote(chars: &[char], start: usize, backslash_escapes: bool) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() {
+        if backslash_escapes && chars[i] == '\\' {
+            i += 2;
+            continue;
+        }
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+fn dollar_tag(chars: &[char], start: usize) -> Option<usize> {
+    if start > 0 && (chars[start - 1].is_alphanumeric() || chars[start - 1] == '_') {
+        return None;
+    }
+    if chars.get(start + 1).is_some_and(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '$' => return Some(i),
+            c if c.is_alphanumeric() || c == '_' => i += 1,
+            _ => return None,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_semicolons_outside_quotes() {
+        let sql = "INSERT INTO \"a;b\" VALUES ('x;y', 'it''s; quoted');\nSELECT 1;";
+        assert_eq!(
+            split_statements(sql, &DriverType::SQLite),
+            vec![
+                "INSERT INTO \"a;b\" VALUES ('x;y', 'it''s; quoted')",
+                "SELECT 1"
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_empty_statements_and_trailing_comments() {
+        let sql = ";;SELECT 1; -- a; b\n/* c; d */;";
+        assert_eq!(split_statements(sql, &DriverType::SQLite), vec!["SELECT 1"]);
+    }
+
+    #[test]
+    fn postgres_honours_backslashes_only_in_escape_strings() {
+        let sql = "SELECT E'it\\'s; quoted'; SELECT 'C:\\'; SELECT 2";
+        assert_eq!(
+            split_statements(sql, &DriverType::PostgreSQL),
+            vec!["SELECT E'it\\'s; quoted'", "SELECT 'C:\\'", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn mysql_honours_backslashes_in_strings() {
+        let sql = "SELECT 'it\\'s; quoted', \"a\\\"; b\"; SELECT 2";
+        assert_eq!(
+            split_statements(sql, &DriverType::MySQL),
+            vec!["SELECT 'it\\'s; quoted', \"a\\\"; b\"", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn keeps_dollar_quoted_bodies_whole() {
+        let function = "CREATE FUNCTION touch() RETURNS trigger AS $body$\n\
+                        BEGIN\n  NEW.updated_at := now();\n  RETURN NEW;\nEND;\n\
+                        $body$ LANGUAGE plpgsql";
+        let sql = format!("{};\nSELECT $$a;b$$, $1", function);
+        assert_eq!(
+            split_statements(&sql, &DriverType::PostgreSQL),
+            vec![function, "SELECT $$a;b$$, $1"]
+        );
+    }
+
+    #[test]
+    fn keeps_sqlite_trigger_bodies_whole() {
+        let trigger = "CREATE TRIGGER touch AFTER UPDATE ON posts\n\
+                       BEGIN\n  UPDATE posts SET updated_at = CURRENT_TIMESTAMP;\n  \
+                       DELETE FROM drafts WHERE post_id = NEW.id;\nEND";
+        let sql = format!("{};\nCREATE INDEX posts_title ON posts (title);", trigger);
+        assert_eq!(
+            split_statements(&sql, &DriverType::SQLite),
+            vec![trigger, "CREATE INDEX posts_title ON posts (title)"]
+        );
+    }
+
+    #[test]
+    fn keeps_mysql_routine_bodies_whole() {
+        let procedure = "CREATE PROCEDURE archive(IN days INT)\n\
+                         BEGIN\n  IF days > 0 THEN\n    DELETE FROM posts;\n  END IF;\n  \
+                         SELECT CASE WHEN days > 1 THEN 'many' ELSE 'one' END;\n  \
+                         WHILE days > 0 DO\n    SET days = days - 1;\n  END WHILE;\nEND";
+        let sql = format!("{};\nCALL archive(3);", procedure);
+        assert_eq!(
+            split_statements(&sql, &DriverType::MySQL),
+            vec![procedure, "CALL archive(3)"]
+        );
+    }
+}
@@ -1,5 +1,8 @@
+use super::queries::SelectQuery;
+pub use crate::domain::query::builder::JoinType;
 use crate::domain::query::builder::{Operator, OrderDirection};
 use crate::domain::value_objects::Value;
+use crate::infrastructure::schema::ColumnType;
 
 #[derive(Debug, Clone)]
 pub enum Expression {
@@ -7,6 +10,17 @@ pub enum Expression {
     QualifiedColumn(String, String),
     Literal(Value),
     Function(String, Vec<Expression>),
+    Subquery(Box<SelectQuery>),
+    Arithmetic(Box<Expression>, ArithmeticOperator, Box<Expression>),
+    // Rendered as a cast only by dialects that need one, see SqlGenerator::cast_type
+    Cast(Box<Expression>, ColumnType),
+    Raw(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArithmeticOperator {
+    Add,
+    Subtract,
 }
 
 #[derive(Debug, Clone)]
@@ -17,22 +31,39 @@ pub struct Condition {
 }
 
 #[derive(Debug, Clone)]
-pub struct OrderBy {
-    pub expression: Expression,
-    pub direction: OrderDirection,
+pub enum ConditionTree {
+    Leaf(Condition),
+    And(Vec<ConditionTree>),
+    Or(Vec<ConditionTree>),
+    Not(Box<ConditionTree>),
+    Exists(Box<SelectQuery>),
+}
+
+impl ConditionTree {
+    pub fn is_compound(&self) -> bool {
+        match self {
+            ConditionTree::And(children) | ConditionTree::Or(children) => children.len() > 1,
+            ConditionTree::Leaf(_) | ConditionTree::Not(_) | ConditionTree::Exists(_) => false,
+        }
+    }
+}
+
+impl From<Condition> for ConditionTree {
+    fn from(condition: Condition) -> Self {
+        ConditionTree::Leaf(condition)
+    }
 }
 
 #[derive(Debug, Clone)]
-pub enum JoinType {
-    Inner,
-    Left,
-    Right,
-    Full,
+pub struct OrderBy {
+    pub expression: Expression,
+    pub direction: OrderDirection,
 }
 
 #[derive(Debug, Clone)]
 pub struct Join {
     pub table: String,
-    pub on: Condition,
+    pub alias: Option<String>,
+    pub on: Option<ConditionTree>,
     pub join_type: JoinType,
 }
@@ -1,12 +1,14 @@
-use super::expressions::{Condition, Expression, Join, OrderBy};
+use super::expressions::{Condition, ConditionTree, Expression, Join, OrderBy};
 
 #[derive(Debug, Clone)]
 pub struct SelectQuery {
     pub table: String,
+    pub alias: Option<String>,
+    pub from_query: Option<Box<SelectQuery>>,
     pub columns: Vec<Expression>,
     pub distinct: bool,
     pub joins: Vec<Join>,
-    pub filters: Vec<Condition>,
+    pub filters: Vec<ConditionTree>,
     pub group_by: Vec<Expression>,
     pub having: Vec<Condition>,
     pub order_by: Vec<OrderBy>,
@@ -18,20 +20,34 @@ pub struct SelectQuery {
 pub struct InsertQuery {
     pub table: String,
     pub columns: Vec<String>,
-    pub values: Vec<crate::domain::value_objects::Value>,
+    pub rows: Vec<Vec<crate::domain::value_objects::Value>>,
+    pub on_conflict: Option<OnConflict>,
+    pub returning: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OnConflict {
+    pub columns: Vec<String>,
+    pub action: ConflictAction,
+}
+
+#[derive(Debug, Clone)]
+pub enum ConflictAction {
+    DoNothing,
+    DoUpdate(Vec<String>),
 }
 
 #[derive(Debug, Clone)]
 pub struct UpdateQuery {
     pub table: String,
-    pub assignments: Vec<(String, crate::domain::value_objects::Value)>,
-    pub filters: Vec<Condition>,
+    pub assignments: Vec<(String, Expression)>,
+    pub filters: Vec<ConditionTree>,
 }
 
 #[derive(Debug, Clone)]
 pub struct DeleteQuery {
     pub table: String,
-    pub filters: Vec<Condition>,
+    pub filters: Vec<ConditionTree>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,66 +1,79 @@
-use crate::domain::model::relationships::RelationshipType;
 use crate::domain::model::Entity;
+use crate::domain::query::assignment::{Assignment, AssignmentValue};
 use crate::domain::query::builder as domain_builder;
+use crate::domain::query::filter::FilterLogic;
+use crate::domain::value_objects::Value;
 use crate::infrastructure::query_builder::ast::*;
+use crate::infrastructure::schema::{ColumnType, TableDefinition};
+use std::collections::HashMap;
 
 pub struct QueryAstBuilder;
 
 impl QueryAstBuilder {
     pub fn build_select<E: Entity>(builder: &domain_builder::QueryBuilder<E>) -> SelectQuery {
-        let columns = if builder.selected_columns.is_empty() {
-            vec![Expression::Column("*".to_string())]
-        } else {
-            builder
+        Self::build_subquery(&domain_builder::Subquery::from(builder))
+    }
+
+    pub fn build_subquery(query: &domain_builder::Subquery) -> SelectQuery {
+        let types = Self::typed_columns((query.table_definition)());
+        let columns = if !query.selected_columns.is_empty() {
+            query
                 .selected_columns
                 .iter()
-                .map(|c| Expression::Column(c.clone()))
+                .map(|c| Self::column_expression(c))
                 .collect()
+        } else if !query.joins.is_empty() {
+            vec![Expression::QualifiedColumn(
+                query.alias.as_ref().unwrap_or(&query.table_name).clone(),
+                "*".to_string(),
+            )]
+        } else {
+            vec![Expression::Column("*".to_string())]
         };
 
         SelectQuery {
-            table: E::table_name().to_string(),
+            table: query.table_name.clone(),
+            alias: query.alias.clone(),
+            from_query: Self::build_from_query(&query.from_query),
             columns,
-            distinct: builder.distinct,
-            joins: Self::build_joins::<E>(builder),
-            filters: builder
-                .filters
-                .iter()
-                .map(|f| Self::build_condition(f))
-                .collect(),
-            group_by: builder
+            distinct: query.distinct,
+            joins: Self::build_joins(&query.joins),
+            filters: Self::build_filters(&query.filters, &types),
+            group_by: query
                 .group_by
                 .iter()
-                .map(|c| Expression::Column(c.clone()))
+                .map(|c| Self::column_expression(c))
                 .collect(),
-            having: builder
+            having: query
                 .having_filters
                 .iter()
-                .map(|f| Self::build_condition(f))
+                .map(|c| Self::build_condition(c, &types))
                 .collect(),
-            order_by: builder
+            order_by: query
                 .order_by
                 .iter()
                 .map(|o| Self::build_order_by(o))
                 .collect(),
-            limit: builder.limit,
-            offset: builder.offset,
+            limit: query.limit,
+            offset: query.offset,
         }
     }
 
     pub fn build_count<E: Entity>(builder: &domain_builder::QueryBuilder<E>) -> SelectQuery {
         SelectQuery {
             table: E::table_name().to_string(),
+            alias: builder.alias.clone(),
+            from_query: Self::build_from_query(&builder.from_query),
             columns: vec![Expression::Function(
                 "COUNT".to_string(),
                 vec![Expression::Column("*".to_string())],
             )],
             distinct: false,
-            joins: Vec::new(),
-            filters: builder
-                .filters
-                .iter()
-                .map(|f| Self::build_condition(f))
-                .collect(),
+            joins: Self::build_joins(&builder.joins),
+            filters: Self::build_filters(
+                &builder.filters,
+                &Self::typed_columns(E::table_definition()),
+            ),
             group_by: Vec::new(),
             having: Vec::new(),
             order_by: Vec::new(),
@@ -76,17 +89,18 @@ impl QueryAstBuilder {
     ) -> SelectQuery {
         SelectQuery {
             table: E::table_name().to_string(),
+            alias: builder.alias.clone(),
+            from_query: Self::build_from_query(&builder.from_query),
             columns: vec![Expression::Function(
                 function.to_string(),
-                vec![Expression::Column(field.to_string())],
+                vec![Self::column_expression(field)],
             )],
             distinct: false,
-            joins: Vec::new(),
-            filters: builder
-                .filters
-                .iter()
-                .map(|f| Self::build_condition(f))
-                .collect(),
+            joins: Self::build_joins(&builder.joins),
+            filters: Self::build_filters(
+                &builder.filters,
+                &Self::typed_columns(E::table_definition()),
+            ),
             group_by: Vec::new(),
             having: Vec::new(),
             order_by: Vec::new(),
@@ -96,22 +110,77 @@ impl QueryAstBuilder {
     }
 
     pub fn build_insert<E: Entity>(entity: &E) -> InsertQuery {
-        let values_map = entity.to_values();
-        let mut columns = Vec::new();
-        let mut values = Vec::new();
+        Self::build_insert_many(std::slice::from_ref(entity))
+    }
 
-        for (col, val) in values_map {
-            columns.push(col);
-            values.push(val);
+    pub fn build_insert_many<E: Entity>(entities: &[E]) -> InsertQuery {
+        let rows: Vec<_> = entities.iter().map(|e| e.to_values()).collect();
+        let pk = E::primary_key();
+
+        let mut columns: Vec<String> = rows
+            .first()
+            .map(|row| row.keys().cloned().collect())
+            .unwrap_or_default();
+        columns.sort();
+        if rows
+            .iter()
+            .all(|row| matches!(row.get(pk), None | Some(Value::Null)))
+        {
+            columns.retain(|c| c != pk);
         }
 
+        let rows = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|c| row.get(c).cloned().unwrap_or(Value::Null))
+                    .collect()
+            })
+            .collect();
+
         InsertQuery {
             table: E::table_name().to_string(),
             columns,
-            values,
+            rows,
+            on_conflict: None,
+            returning: Vec::new(),
         }
     }
 
+    pub fn build_upsert<E: Entity>(
+        entities: &[E],
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> InsertQuery {
+        let mut query = Self::build_insert_many(entities);
+        let conflict_columns: Vec<String> =
+            conflict_columns.iter().map(|c| c.to_string()).collect();
+
+        let update_columns: Vec<String> = if update_columns.is_empty() {
+            query
+                .columns
+                .iter()
+                .filter(|c| !conflict_columns.contains(c) && c.as_str() != E::primary_key())
+                .cloned()
+                .collect()
+        } else {
+            update_columns.iter().map(|c| c.to_string()).collect()
+        };
+
+        let action = if update_columns.is_empty() {
+            ConflictAction::DoNothing
+        } else {
+            ConflictAction::DoUpdate(update_columns)
+        };
+
+        query.on_conflict = Some(OnConflict {
+            columns: conflict_columns,
+            action,
+        });
+        query
+    }
+
     pub fn build_update<E: Entity>(entity: &E) -> UpdateQuery {
         let values_map = entity.to_values();
         let pk_name = E::primary_key();
@@ -123,16 +192,16 @@ impl QueryAstBuilder {
                 pk_value = Some(val);
                 continue;
             }
-            assignments.push((col, val));
+            assignments.push((col, Expression::Literal(val)));
         }
 
         let mut filters = Vec::new();
         if let Some(val) = pk_value {
-            filters.push(Condition {
+            filters.push(ConditionTree::Leaf(Condition {
                 left: Expression::Column(pk_name.to_string()),
                 operator: domain_builder::Operator::Eq,
                 right: vec![Expression::Literal(val)],
-            });
+            }));
         }
 
         UpdateQuery {
@@ -145,120 +214,213 @@ impl QueryAstBuilder {
     pub fn build_delete<E: Entity>(entity: &E) -> DeleteQuery {
         let values_map = entity.to_values();
         let pk_name = E::primary_key();
-        let pk_value = values_map
-            .get(pk_name)
-            .cloned()
-            .unwrap_or(crate::domain::value_objects::Value::Null);
+        let pk_value = values_map.get(pk_name).cloned().unwrap_or(Value::Null);
 
         DeleteQuery {
             table: E::table_name().to_string(),
-            filters: vec![Condition {
+            filters: vec![ConditionTree::Leaf(Condition {
                 left: Expression::Column(pk_name.to_string()),
                 operator: domain_builder::Operator::Eq,
                 right: vec![Expression::Literal(pk_value)],
-            }],
+            })],
+        }
+    }
+
+    pub fn build_update_where<E: Entity>(
+        builder: &domain_builder::QueryBuilder<E>,
+        assignments: &[Assignment],
+    ) -> UpdateQuery {
+        UpdateQuery {
+            table: E::table_name().to_string(),
+            assignments: assignments
+                .iter()
+                .map(|a| (a.column.clone(), Self::build_assignment(a)))
+                .collect(),
+            filters: Self::build_filters(
+                &builder.filters,
+                &Self::typed_columns(E::table_definition()),
+            ),
         }
     }
 
-    fn build_joins<E: Entity>(builder: &domain_builder::QueryBuilder<E>) -> Vec<Join> {
-        let mut joins = Vec::new();
-        let relationships = E::relationships();
-        let base_table = E::table_name();
-
-        for rel_name in &builder.with_relations {
-            if let Some(rel) = relationships.get(rel_name) {
-                match rel.rel_type {
-                    RelationshipType::BelongsTo => {
-                        joins.push(Join {
-                            table: rel.target_table.clone(),
-                            join_type: JoinType::Left,
-                            on: Condition {
-                                left: Expression::QualifiedColumn(
-                                    base_table.to_string(),
-                                    rel.foreign_key.clone(),
-                                ),
-                                operator: domain_builder::Operator::Eq,
-                                right: vec![Expression::QualifiedColumn(
-                                    rel.target_table.clone(),
-                                    "id".to_string(),
-                                )],
-                            },
-                        });
-                    }
-                    RelationshipType::HasMany | RelationshipType::HasOne => {
-                        joins.push(Join {
-                            table: rel.target_table.clone(),
-                            join_type: JoinType::Left,
-                            on: Condition {
-                                left: Expression::QualifiedColumn(
-                                    rel.target_table.clone(),
-                                    rel.foreign_key.clone(),
-                                ),
-                                operator: domain_builder::Operator::Eq,
-                                right: vec![Expression::QualifiedColumn(
-                                    base_table.to_string(),
-                                    "id".to_string(),
-                                )],
-                            },
-                        });
-                    }
-                    RelationshipType::ManyToMany => {
-                        let join_table = rel.join_table.as_ref().unwrap();
-                        let target_fk = rel.target_foreign_key.as_ref().unwrap();
-
-                        joins.push(Join {
-                            table: join_table.clone(),
-                            join_type: JoinType::Left,
-                            on: Condition {
-                                left: Expression::QualifiedColumn(
-                                    join_table.clone(),
-                                    rel.foreign_key.clone(),
-                                ),
-                                operator: domain_builder::Operator::Eq,
-                                right: vec![Expression::QualifiedColumn(
-                                    base_table.to_string(),
-                                    "id".to_string(),
-                                )],
-                            },
-                        });
-
-                        joins.push(Join {
-                            table: rel.target_table.clone(),
-                            join_type: JoinType::Left,
-                            on: Condition {
-                                left: Expression::QualifiedColumn(
-                                    rel.target_table.clone(),
-                                    "id".to_string(),
-                                ),
-                                operator: domain_builder::Operator::Eq,
-                                right: vec![Expression::QualifiedColumn(
-                                    join_table.clone(),
-                                    target_fk.clone(),
-                                )],
-                            },
-                        });
-                    }
+    pub fn build_delete_where<E: Entity>(builder: &domain_builder::QueryBuilder<E>) -> DeleteQuery {
+        DeleteQuery {
+            table: E::table_name().to_string(),
+            filters: Self::build_filters(
+                &builder.filters,
+                &Self::typed_columns(E::table_definition()),
+            ),
+        }
+    }
+
+    fn build_assignment(assignment: &Assignment) -> Expression {
+        let column = || Box::new(Expression::Column(assignment.column.clone()));
+        match &assignment.value {
+            AssignmentValue::Value(val) => Expression::Literal(val.clone()),
+            AssignmentValue::Column(source) => Self::column_expression(source),
+            AssignmentValue::Increment(amount) => Expression::Arithmetic(
+                column(),
+                ArithmeticOperator::Add,
+                Box::new(Expression::Literal(amount.clone())),
+            ),
+            AssignmentValue::Decrement(amount) => Expression::Arithmetic(
+                column(),
+                ArithmeticOperator::Subtract,
+                Box::new(Expression::Literal(amount.clone())),
+            ),
+            AssignmentValue::Raw(sql) => Expression::Raw(sql.clone()),
+        }
+    }
+
+    fn build_joins(joins: &[domain_builder::JoinClause]) -> Vec<Join> {
+        joins
+            .iter()
+            .map(|join| Join {
+                table: join.table.clone(),
+                alias: join.alias.clone(),
+                on: Self::build_where(&join.on, &HashMap::new()),
+                join_type: join.join_type,
+            })
+            .collect()
+    }
+
+    fn build_from_query(
+        from_query: &Option<Box<domain_builder::Subquery>>,
+    ) -> Option<Box<SelectQuery>> {
+        from_query
+            .as_deref()
+            .map(|query| Box::new(Self::build_subquery(query)))
+    }
+
+    fn column_expression(column: &str) -> Expression {
+        match column.split_once('.') {
+            Some((table, column)) => {
+                Expression::QualifiedColumn(table.to_string(), column.to_string())
+            }
+            None => Expression::Column(column.to_string()),
+        }
+    }
+
+    // Enum and array columns, whose operands some dialects must cast
+    fn typed_columns(table: TableDefinition) -> HashMap<String, ColumnType> {
+        table
+            .columns
+            .into_iter()
+            .filter(|c| {
+                matches!(
+                    c.column_type,
+                    ColumnType::Array(_) | ColumnType::Enum { .. }
+                )
+            })
+            .map(|c| (c.name, c.column_type))
+            .collect()
+    }
+
+    fn build_filters(
+        clauses: &[domain_builder::WhereClause],
+        types: &HashMap<String, ColumnType>,
+    ) -> Vec<ConditionTree> {
+        Self::build_where(clauses, types).into_iter().collect()
+    }
+
+    fn build_where(
+        clauses: &[domain_builder::WhereClause],
+        types: &HashMap<String, ColumnType>,
+    ) -> Option<ConditionTree> {
+        let mut branches: Vec<Vec<ConditionTree>> = Vec::new();
+
+        for clause in clauses {
+            let Some(node) = Self::build_where_node(clause, types) else {
+                continue;
+            };
+            match branches.last_mut() {
+                Some(branch) if clause.logic == FilterLogic::And => branch.push(node),
+                _ => branches.push(vec![node]),
+            }
+        }
+
+        let mut branches: Vec<ConditionTree> = branches
+            .into_iter()
+            .map(|mut branch| {
+                if branch.len() == 1 {
+                    branch.remove(0)
+                } else {
+                    ConditionTree::And(branch)
                 }
+            })
+            .collect();
+
+        match branches.len() {
+            0 => None,
+            1 => branches.pop(),
+            _ => Some(ConditionTree::Or(branches)),
+        }
+    }
+
+    fn build_where_node(
+        clause: &domain_builder::WhereClause,
+        types: &HashMap<String, ColumnType>,
+    ) -> Option<ConditionTree> {
+        let tree = match &clause.node {
+            domain_builder::WhereNode::Condition(condition) => {
+                ConditionTree::Leaf(Self::build_condition(condition, types))
+            }
+            domain_builder::WhereNode::Column(condition) => ConditionTree::Leaf(Condition {
+                left: Self::column_expression(&condition.left),
+                operator: condition.operator,
+                right: vec![Self::column_expression(&condition.right)],
+            }),
+            domain_builder::WhereNode::Subquery(condition) => ConditionTree::Leaf(Condition {
+                left: Self::column_expression(&condition.column),
+                operator: condition.operator,
+                right: vec![Expression::Subquery(Box::new(Self::build_subquery(
+                    &condition.query,
+                )))],
+            }),
+            domain_builder::WhereNode::Exists(query) => {
+                ConditionTree::Exists(Box::new(Self::build_subquery(query)))
             }
+            domain_builder::WhereNode::Group(children) => Self::build_where(children, types)?,
+        };
+
+        if clause.negated {
+            Some(ConditionTree::Not(Box::new(tree)))
+        } else {
+            Some(tree)
         }
-        joins
     }
 
-    fn build_condition(filter: &domain_builder::Condition) -> Condition {
+    fn build_condition(
+        filter: &domain_builder::Condition,
+        types: &HashMap<String, ColumnType>,
+    ) -> Condition {
+        use domain_builder::Operator;
+
+        let cast = match (filter.operator, types.get(&filter.column)) {
+            (Operator::Any, Some(ColumnType::Array(element))) => Some(element.as_ref()),
+            (Operator::Like | Operator::NotLike | Operator::ILike, _) => None,
+            (_, column_type) => column_type,
+        };
         Condition {
-            left: Expression::Column(filter.column.clone()),
+            left: Self::column_expression(&filter.column),
             operator: filter.operator,
             right: filter
                 .values
                 .iter()
-                .map(|v| Expression::Literal(v.clone()))
+                .map(|v| match cast {
+                    Some(column_type) if *v != Value::Null => Expression::Cast(
+                        Box::new(Expression::Literal(v.clone())),
+                        column_type.clone(),
+                    ),
+                    _ => Expression::Literal(v.clone()),
+                })
                 .collect(),
         }
     }
 
     fn build_order_by(order: &domain_builder::OrderBy) -> OrderBy {
         OrderBy {
-            expression: Expression::Column(order.column.clone()),
+            expression: Self::column_expression(&order.column),
             direction: order.direction,
         }
     }
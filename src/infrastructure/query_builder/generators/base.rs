@@ -1,4 +1,4 @@
-use super::config::GeneratorConfig;
+use super::config::{ArrayStyle, GeneratorConfig, UpsertStyle};
 use super::sql_generator::SqlGenerator;
 use crate::infrastructure::schema::{ColumnType, TableDefinition};
 
@@ -50,6 +50,38 @@ impl SqlGenerator for BaseGenerator {
         self.config.table_options.to_string()
     }
 
+    fn supports_ilike(&self) -> bool {
+        self.config.supports_ilike
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        self.config.upsert_style
+    }
+
+    fn supports_returning(&self) -> bool {
+        self.config.supports_returning
+    }
+
+    fn max_bind_params(&self) -> usize {
+        self.config.max_bind_params
+    }
+
+    fn last_insert_id_sql(&self) -> String {
+        self.config.last_insert_id_sql.to_string()
+    }
+
+    fn supports_transactional_ddl(&self) -> bool {
+        self.config.supports_transactional_ddl
+    }
+
+    fn array_style(&self) -> ArrayStyle {
+        self.config.array_style
+    }
+
+    fn cast_type(&self, col_type: &ColumnType) -> Option<String> {
+        self.config.cast_type(col_type)
+    }
+
     fn generate_create_table(&self, table: &TableDefinition) -> String {
         if self.config.name == "PostgreSQL" {
             return self.generate_postgres_create_table(table);
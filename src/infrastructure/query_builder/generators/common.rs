@@ -1,7 +1,7 @@
 use crate::domain::query::builder::{Operator, OrderDirection};
 use crate::domain::value_objects::Value;
 use crate::infrastructure::query_builder::ast::*;
-use crate::infrastructure::query_builder::generators::SqlGenerator;
+use crate::infrastructure::query_builder::generators::{ArrayStyle, SqlGenerator, UpsertStyle};
 
 pub struct CommonGenerator;
 
@@ -10,8 +10,17 @@ impl CommonGenerator {
         generator: &G,
         query: &SelectQuery,
     ) -> (String, Vec<Value>) {
-        let mut sql = String::from("SELECT ");
         let mut params = Vec::new();
+        let sql = Self::select_to_sql(generator, query, &mut params);
+        (sql, params)
+    }
+
+    fn select_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        query: &SelectQuery,
+        params: &mut Vec<Value>,
+    ) -> String {
+        let mut sql = String::from("SELECT ");
 
         if query.distinct {
             sql.push_str("DISTINCT ");
@@ -20,12 +29,27 @@ impl CommonGenerator {
         let columns: Vec<String> = query
             .columns
             .iter()
-            .map(|e| Self::expression_to_sql(generator, e))
+            .map(|e| Self::expression_to_sql(generator, e, params))
             .collect();
         sql.push_str(&columns.join(", "));
 
         sql.push_str(" FROM ");
-        sql.push_str(&generator.quote_identifier(&query.table));
+        match &query.from_query {
+            Some(from_query) => {
+                sql.push_str(&format!(
+                    "({})",
+                    Self::select_to_sql(generator, from_query, params)
+                ));
+                if let Some(alias) = &query.alias {
+                    sql.push_str(&format!(" AS {}", generator.quote_identifier(alias)));
+                }
+            }
+            None => sql.push_str(&Self::table_to_sql(
+                generator,
+                &query.table,
+                query.alias.as_deref(),
+            )),
+        }
 
         for join in &query.joins {
             let join_type = match join.join_type {
@@ -35,21 +59,19 @@ impl CommonGenerator {
                 JoinType::Full => "FULL OUTER JOIN",
             };
             sql.push_str(&format!(
-                " {} {} ON ",
+                " {} {}",
                 join_type,
-                generator.quote_identifier(&join.table)
+                Self::table_to_sql(generator, &join.table, join.alias.as_deref())
             ));
-            let cond_sql = Self::condition_to_sql(generator, &join.on, &mut params);
-            sql.push_str(&cond_sql);
+            if let Some(on) = &join.on {
+                sql.push_str(" ON ");
+                sql.push_str(&Self::condition_tree_to_sql(generator, on, false, params));
+            }
         }
 
         if !query.filters.is_empty() {
             sql.push_str(" WHERE ");
-            let mut filter_sqls = Vec::new();
-            for filter in &query.filters {
-                filter_sqls.push(Self::condition_to_sql(generator, filter, &mut params));
-            }
-            sql.push_str(&filter_sqls.join(" AND "));
+            sql.push_str(&Self::conditions_to_sql(generator, &query.filters, params));
         }
 
         if !query.group_by.is_empty() {
@@ -57,36 +79,37 @@ impl CommonGenerator {
             let groups: Vec<String> = query
                 .group_by
                 .iter()
-                .map(|e| Self::expression_to_sql(generator, e))
+                .map(|e| Self::expression_to_sql(generator, e, params))
                 .collect();
             sql.push_str(&groups.join(", "));
         }
 
-        if !query.order_by.is_empty() {
-            sql.push_str(" ORDER BY ");
-            let orders: Vec<String> = query
-                .order_by
-                .iter()
-                .map(|o| {
-                    let dir = match o.direction {
-                        OrderDirection::Asc => "ASC",
-                        OrderDirection::Desc => "DESC",
-                    };
-                    format!("{} {}", Self::expression_to_sql(generator, &o.expression), dir)
-                })
-                .collect();
-            sql.push_str(&orders.join(", "));
-        }
-
         if !query.having.is_empty() {
             sql.push_str(" HAVING ");
             let mut filter_sqls = Vec::new();
             for filter in &query.having {
-                filter_sqls.push(Self::condition_to_sql(generator, filter, &mut params));
+                filter_sqls.push(Self::condition_to_sql(generator, filter, params));
             }
             sql.push_str(&filter_sqls.join(" AND "));
         }
 
+        if !query.order_by.is_empty() {
+            sql.push_str(" ORDER BY ");
+            let mut orders = Vec::new();
+            for o in &query.order_by {
+                let dir = match o.direction {
+                    OrderDirection::Asc => "ASC",
+                    OrderDirection::Desc => "DESC",
+                };
+                orders.push(format!(
+                    "{} {}",
+                    Self::expression_to_sql(generator, &o.expression, params),
+                    dir
+                ));
+            }
+            sql.push_str(&orders.join(", "));
+        }
+
         if let Some(limit) = query.limit {
             sql.push_str(&format!(" LIMIT {}", limit));
         }
@@ -95,7 +118,7 @@ impl CommonGenerator {
             sql.push_str(&format!(" OFFSET {}", offset));
         }
 
-        (sql, params)
+        sql
     }
 
     pub fn generate_insert<G: SqlGenerator + ?Sized>(
@@ -107,18 +130,105 @@ impl CommonGenerator {
             .iter()
             .map(|c| generator.quote_identifier(c))
             .collect();
-        let placeholders: Vec<String> = (0..query.columns.len())
-            .map(|i| generator.placeholder(i))
-            .collect();
 
-        let sql = format!(
-            "INSERT INTO {} ({}) VALUES ({})",
+        let mut params = Vec::new();
+        let mut rows = Vec::new();
+        for row in &query.rows {
+            let mut placeholders = Vec::new();
+            for val in row {
+                placeholders.push(Self::value_to_sql(generator, val, &mut params));
+            }
+            rows.push(format!("({})", placeholders.join(", ")));
+        }
+
+        let mut sql = format!(
+            "INSERT INTO {} ({}) VALUES {}",
             generator.quote_identifier(&query.table),
             columns.join(", "),
-            placeholders.join(", ")
+            rows.join(", ")
         );
 
-        (sql, query.values.clone())
+        if let Some(on_conflict) = &query.on_conflict {
+            sql.push_str(&Self::on_conflict_to_sql(generator, query, on_conflict));
+        }
+
+        if !query.returning.is_empty() && generator.supports_returning() {
+            let returning: Vec<String> = query
+                .returning
+                .iter()
+                .map(|c| generator.quote_identifier(c))
+                .collect();
+            sql.push_str(&format!(" RETURNING {}", returning.join(", ")));
+        }
+
+        (sql, params)
+    }
+
+    fn on_conflict_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        query: &InsertQuery,
+        on_conflict: &OnConflict,
+    ) -> String {
+        let quote_all = |columns: &[String]| -> Vec<String> {
+            columns
+                .iter()
+                .map(|c| generator.quote_identifier(c))
+                .collect()
+        };
+
+        match generator.upsert_style() {
+            UpsertStyle::OnConflict => {
+                let target = if on_conflict.columns.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", quote_all(&on_conflict.columns).join(", "))
+                };
+                match &on_conflict.action {
+                    ConflictAction::DoNothing => format!(" ON CONFLICT{} DO NOTHING", target),
+                    ConflictAction::DoUpdate(columns) => {
+                        let sets: Vec<String> = quote_all(columns)
+                            .iter()
+                            .map(|c| format!("{} = EXCLUDED.{}", c, c))
+                            .collect();
+                        format!(" ON CONFLICT{} DO UPDATE SET {}", target, sets.join(", "))
+                    }
+                }
+            }
+            UpsertStyle::OnDuplicateKey => {
+                let sets: Vec<String> = match &on_conflict.action {
+                    ConflictAction::DoNothing => {
+                        let column = on_conflict
+                            .columns
+                            .first()
+                            .or_else(|| query.columns.first())
+                            .map(|c| generator.quote_identifier(c))
+                            .unwrap_or_default();
+                        vec![format!("{} = {}", column, column)]
+                    }
+                    ConflictAction::DoUpdate(columns) => quote_all(columns)
+                        .iter()
+                        .map(|c| format!("{} = VALUES({})", c, c))
+                        .collect(),
+                };
+                format!(" ON DUPLICATE KEY UPDATE {}", sets.join(", "))
+            }
+        }
+    }
+
+    // PostgreSQL statements are prepared once per SQL text, and an empty array
+    // has no element type to bind with, so it is written inline to coerce
+    fn value_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        val: &Value,
+        params: &mut Vec<Value>,
+    ) -> String {
+        if generator.array_style() == ArrayStyle::Native
+            && matches!(val, Value::Array(items) if items.is_empty())
+        {
+            return "'{}'".to_string();
+        }
+        params.push(val.clone());
+        generator.placeholder(params.len() - 1)
     }
 
     pub fn generate_update<G: SqlGenerator + ?Sized>(
@@ -128,13 +238,12 @@ impl CommonGenerator {
         let mut params = Vec::new();
         let mut assignments = Vec::new();
 
-        for (i, (col, val)) in query.assignments.iter().enumerate() {
+        for (col, expr) in &query.assignments {
             assignments.push(format!(
                 "{} = {}",
                 generator.quote_identifier(col),
-                generator.placeholder(i)
+                Self::expression_to_sql(generator, expr, &mut params)
             ));
-            params.push(val.clone());
         }
 
         let mut sql = format!(
@@ -145,11 +254,11 @@ impl CommonGenerator {
 
         if !query.filters.is_empty() {
             sql.push_str(" WHERE ");
-            let mut filter_sqls = Vec::new();
-            for filter in &query.filters {
-                filter_sqls.push(Self::condition_to_sql(generator, filter, &mut params));
-            }
-            sql.push_str(&filter_sqls.join(" AND "));
+            sql.push_str(&Self::conditions_to_sql(
+                generator,
+                &query.filters,
+                &mut params,
+            ));
         }
 
         (sql, params)
@@ -164,17 +273,36 @@ impl CommonGenerator {
 
         if !query.filters.is_empty() {
             sql.push_str(" WHERE ");
-            let mut filter_sqls = Vec::new();
-            for filter in &query.filters {
-                filter_sqls.push(Self::condition_to_sql(generator, filter, &mut params));
-            }
-            sql.push_str(&filter_sqls.join(" AND "));
+            sql.push_str(&Self::conditions_to_sql(
+                generator,
+                &query.filters,
+                &mut params,
+            ));
         }
 
         (sql, params)
     }
 
-    fn expression_to_sql<G: SqlGenerator + ?Sized>(generator: &G, expr: &Expression) -> String {
+    fn table_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        table: &str,
+        alias: Option<&str>,
+    ) -> String {
+        match alias {
+            Some(alias) => format!(
+                "{} AS {}",
+                generator.quote_identifier(table),
+                generator.quote_identifier(alias)
+            ),
+            None => generator.quote_identifier(table),
+        }
+    }
+
+    fn expression_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        expr: &Expression,
+        params: &mut Vec<Value>,
+    ) -> String {
         match expr {
             Expression::Column(col) => {
                 if col == "*" {
@@ -190,14 +318,89 @@ impl CommonGenerator {
                     generator.quote_identifier(col)
                 )
             }
-            Expression::Literal(val) => val.to_string(),
+            Expression::Literal(val) => Self::value_to_sql(generator, val, params),
             Expression::Function(name, args) => {
                 let arg_sqls: Vec<String> = args
                     .iter()
-                    .map(|e| Self::expression_to_sql(generator, e))
+                    .map(|e| Self::expression_to_sql(generator, e, params))
                     .collect();
                 format!("{}({})", name, arg_sqls.join(", "))
             }
+            Expression::Subquery(query) => {
+                format!("({})", Self::select_to_sql(generator, query, params))
+            }
+            Expression::Arithmetic(left, op, right) => {
+                let op = match op {
+                    ArithmeticOperator::Add => "+",
+                    ArithmeticOperator::Subtract => "-",
+                };
+                format!(
+                    "{} {} {}",
+                    Self::expression_to_sql(generator, left, params),
+                    op,
+                    Self::expression_to_sql(generator, right, params)
+                )
+            }
+            Expression::Cast(inner, column_type) => {
+                let sql = Self::expression_to_sql(generator, inner, params);
+                match generator.cast_type(column_type) {
+                    Some(sql_type) => format!("CAST({} AS {})", sql, sql_type),
+                    None => sql,
+                }
+            }
+            Expression::Raw(sql) => sql.clone(),
+        }
+    }
+
+    fn conditions_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        filters: &[ConditionTree],
+        params: &mut Vec<Value>,
+    ) -> String {
+        Self::join_condition_trees(generator, filters, " AND ", params)
+    }
+
+    fn join_condition_trees<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        trees: &[ConditionTree],
+        separator: &str,
+        params: &mut Vec<Value>,
+    ) -> String {
+        let wrap = trees.len() > 1;
+        let mut parts = Vec::new();
+        for tree in trees {
+            parts.push(Self::condition_tree_to_sql(generator, tree, wrap, params));
+        }
+        parts.join(separator)
+    }
+
+    fn condition_tree_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        tree: &ConditionTree,
+        wrap: bool,
+        params: &mut Vec<Value>,
+    ) -> String {
+        let sql = match tree {
+            ConditionTree::Leaf(cond) => Self::condition_to_sql(generator, cond, params),
+            ConditionTree::And(children) => {
+                Self::join_condition_trees(generator, children, " AND ", params)
+            }
+            ConditionTree::Or(children) => {
+                Self::join_condition_trees(generator, children, " OR ", params)
+            }
+            ConditionTree::Not(inner) => format!(
+                "NOT ({})",
+                Self::condition_tree_to_sql(generator, inner, false, params)
+            ),
+            ConditionTree::Exists(query) => {
+                format!("EXISTS ({})", Self::select_to_sql(generator, query, params))
+            }
+        };
+
+        if wrap && tree.is_compound() {
+            format!("({})", sql)
+        } else {
+            sql
         }
     }
 
@@ -206,40 +409,109 @@ impl CommonGenerator {
         cond: &Condition,
         params: &mut Vec<Value>,
     ) -> String {
-        let left = Self::expression_to_sql(generator, &cond.left);
-        let op = match cond.operator {
-            Operator::Eq => "=",
-            Operator::Ne => "!=",
-            Operator::Gt => ">",
-            Operator::Lt => "<",
-            Operator::Gte => ">=",
-            Operator::Lte => "<=",
-            Operator::Like => "LIKE",
-            Operator::In => "IN",
-        };
+        let left = Self::expression_to_sql(generator, &cond.left, params);
+        let null_operand = cond
+            .right
+            .first()
+            .is_none_or(|e| matches!(e, Expression::Literal(Value::Null)));
 
-        if cond.operator == Operator::In {
-            let mut placeholders = Vec::new();
-            for expr in &cond.right {
-                if let Expression::Literal(val) = expr {
-                    placeholders.push(generator.placeholder(params.len()));
-                    params.push(val.clone());
+        match cond.operator {
+            Operator::IsNull => format!("{} IS NULL", left),
+            Operator::IsNotNull => format!("{} IS NOT NULL", left),
+            Operator::Eq if null_operand => format!("{} IS NULL", left),
+            Operator::Ne if null_operand => format!("{} IS NOT NULL", left),
+            Operator::In | Operator::NotIn => {
+                if cond.right.is_empty() {
+                    return if cond.operator == Operator::In {
+                        "1 = 0".to_string()
+                    } else {
+                        "1 = 1".to_string()
+                    };
                 }
+
+                let keyword = if cond.operator == Operator::In {
+                    "IN"
+                } else {
+                    "NOT IN"
+                };
+                if let [Expression::Subquery(query)] = cond.right.as_slice() {
+                    let sub_sql = Self::select_to_sql(generator, query, params);
+                    return format!("{} {} ({})", left, keyword, sub_sql);
+                }
+
+                let operands: Vec<String> = cond
+                    .right
+                    .iter()
+                    .map(|e| Self::expression_to_sql(generator, e, params))
+                    .collect();
+                format!("{} {} ({})", left, keyword, operands.join(", "))
             }
-            format!("{} IN ({})", left, placeholders.join(", "))
-        } else {
-            let right = if let Some(expr) = cond.right.get(0) {
-                match expr {
-                    Expression::Literal(val) => {
-                        params.push(val.clone());
-                        generator.placeholder(params.len() - 1)
+            Operator::Between => {
+                let low = Self::nth_operand_to_sql(generator, cond, 0, params);
+                let high = Self::nth_operand_to_sql(generator, cond, 1, params);
+                format!("{} BETWEEN {} AND {}", left, low, high)
+            }
+            Operator::ILike if !generator.supports_ilike() => {
+                let right = Self::nth_operand_to_sql(generator, cond, 0, params);
+                format!("LOWER({}) LIKE LOWER({})", left, right)
+            }
+            Operator::Contains => {
+                let right = Self::nth_operand_to_sql(generator, cond, 0, params);
+                match generator.array_style() {
+                    ArrayStyle::Native => format!("{} @> {}", left, right),
+                    ArrayStyle::JsonContains => format!("JSON_CONTAINS({}, {})", left, right),
+                    ArrayStyle::JsonEach => format!(
+                        "NOT EXISTS (SELECT 1 FROM json_each({}) \
+                         WHERE value NOT IN (SELECT value FROM json_each({})))",
+                        right, left
+                    ),
+                }
+            }
+            Operator::Any => {
+                let right = Self::nth_operand_to_sql(generator, cond, 0, params);
+                match generator.array_style() {
+                    ArrayStyle::Native => format!("{} = ANY({})", right, left),
+                    ArrayStyle::JsonContains => {
+                        format!("JSON_CONTAINS({}, JSON_ARRAY({}))", left, right)
                     }
-                    _ => Self::expression_to_sql(generator, expr),
+                    ArrayStyle::JsonEach => format!(
+                        "EXISTS (SELECT 1 FROM json_each({}) WHERE value = {})",
+                        left, right
+                    ),
                 }
-            } else {
-                "NULL".to_string()
-            };
-            format!("{} {} {}", left, op, right)
+            }
+            Operator::Eq => Self::comparison_to_sql(generator, left, "=", cond, params),
+            Operator::Ne => Self::comparison_to_sql(generator, left, "!=", cond, params),
+            Operator::Gt => Self::comparison_to_sql(generator, left, ">", cond, params),
+            Operator::Lt => Self::comparison_to_sql(generator, left, "<", cond, params),
+            Operator::Gte => Self::comparison_to_sql(generator, left, ">=", cond, params),
+            Operator::Lte => Self::comparison_to_sql(generator, left, "<=", cond, params),
+            Operator::Like => Self::comparison_to_sql(generator, left, "LIKE", cond, params),
+            Operator::NotLike => Self::comparison_to_sql(generator, left, "NOT LIKE", cond, params),
+            Operator::ILike => Self::comparison_to_sql(generator, left, "ILIKE", cond, params),
+        }
+    }
+
+    fn comparison_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        left: String,
+        op: &str,
+        cond: &Condition,
+        params: &mut Vec<Value>,
+    ) -> String {
+        let right = Self::nth_operand_to_sql(generator, cond, 0, params);
+        format!("{} {} {}", left, op, right)
+    }
+
+    fn nth_operand_to_sql<G: SqlGenerator + ?Sized>(
+        generator: &G,
+        cond: &Condition,
+        index: usize,
+        params: &mut Vec<Value>,
+    ) -> String {
+        match cond.right.get(index) {
+            Some(expr) => Self::expression_to_sql(generator, expr, params),
+            None => "NULL".to_string(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::infrastructure::schema::generators::DdlConfig;
 use crate::infrastructure::schema::ColumnType;
 
 #[derive(Clone)]
@@ -8,6 +9,13 @@ pub struct GeneratorConfig {
     pub type_mapper: TypeMapper,
     pub primary_key_suffix: &'static str,
     pub table_options: &'static str,
+    pub supports_ilike: bool,
+    pub upsert_style: UpsertStyle,
+    pub supports_returning: bool,
+    pub max_bind_params: usize,
+    pub last_insert_id_sql: &'static str,
+    pub supports_transactional_ddl: bool,
+    pub array_style: ArrayStyle,
 }
 
 #[derive(Clone)]
@@ -16,6 +24,22 @@ pub enum PlaceholderStyle {
     Question,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum UpsertStyle {
+    OnConflict,
+    OnDuplicateKey,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ArrayStyle {
+    // Native arrays and enums, with @> and ANY
+    Native,
+    // Arrays stored as JSON, searched with JSON_CONTAINS
+    JsonContains,
+    // Arrays stored as JSON text, searched with json_each
+    JsonEach,
+}
+
 #[derive(Clone)]
 pub enum QuoteStyle {
     DoubleQuote,
@@ -57,6 +81,13 @@ impl TypeMapper {
             ColumnType::NaiveDateTime => "DATETIME",
             ColumnType::Json => "JSON",
             ColumnType::Binary => "BLOB",
+            ColumnType::Decimal => "DECIMAL(38, 10)",
+            ColumnType::Uuid => "CHAR(36)",
+            ColumnType::Date => "DATE",
+            ColumnType::Time => "TIME",
+            ColumnType::Interval => "VARCHAR(64)",
+            ColumnType::Array(_) => "JSON",
+            ColumnType::Enum { .. } => "VARCHAR(255)",
         }
     }
 }
@@ -81,6 +112,13 @@ impl GeneratorConfig {
                 .with_mapping(ColumnType::Binary, "LONGBLOB"),
             primary_key_suffix: " AUTO_INCREMENT",
             table_options: " ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci",
+            supports_ilike: false,
+            upsert_style: UpsertStyle::OnDuplicateKey,
+            supports_returning: false,
+            max_bind_params: 65535,
+            last_insert_id_sql: "SELECT LAST_INSERT_ID() AS last_insert_id",
+            supports_transactional_ddl: false,
+            array_style: ArrayStyle::JsonContains,
         }
     }
 
@@ -95,9 +133,19 @@ impl GeneratorConfig {
                 .with_mapping(ColumnType::DateTime, "TIMESTAMP WITH TIME ZONE")
                 .with_mapping(ColumnType::NaiveDateTime, "TIMESTAMP")
                 .with_mapping(ColumnType::Json, "JSONB")
-                .with_mapping(ColumnType::Binary, "BYTEA"),
+                .with_mapping(ColumnType::Binary, "BYTEA")
+                .with_mapping(ColumnType::Decimal, "NUMERIC")
+                .with_mapping(ColumnType::Uuid, "UUID")
+                .with_mapping(ColumnType::Interval, "INTERVAL"),
             primary_key_suffix: "",
             table_options: ";",
+            supports_ilike: true,
+            upsert_style: UpsertStyle::OnConflict,
+            supports_returning: true,
+            max_bind_params: 65535,
+            last_insert_id_sql: "SELECT lastval() AS last_insert_id",
+            supports_transactional_ddl: true,
+            array_style: ArrayStyle::Native,
         }
     }
 
@@ -115,9 +163,21 @@ impl GeneratorConfig {
                 .with_mapping(ColumnType::DateTime, "TEXT")
                 .with_mapping(ColumnType::NaiveDateTime, "TEXT")
                 .with_mapping(ColumnType::Json, "TEXT")
-                .with_mapping(ColumnType::Binary, "BLOB"),
+                .with_mapping(ColumnType::Binary, "BLOB")
+                .with_mapping(ColumnType::Decimal, "TEXT")
+                .with_mapping(ColumnType::Uuid, "TEXT")
+                .with_mapping(ColumnType::Date, "TEXT")
+                .with_mapping(ColumnType::Time, "TEXT")
+                .with_mapping(ColumnType::Interval, "TEXT"),
             primary_key_suffix: " AUTOINCREMENT",
             table_options: ";",
+            supports_ilike: false,
+            upsert_style: UpsertStyle::OnConflict,
+            supports_returning: true,
+            max_bind_params: 32766,
+            last_insert_id_sql: "SELECT last_insert_rowid() AS last_insert_id",
+            supports_transactional_ddl: true,
+            array_style: ArrayStyle::JsonEach,
         }
     }
 
@@ -144,4 +204,18 @@ impl GeneratorConfig {
     pub fn map_type(&self, col_type: &ColumnType) -> &'static str {
         self.type_mapper.map(col_type)
     }
+
+    // PostgreSQL has no operators between enums and text, and compares arrays
+    // only when their element types match, so such operands are cast to the
+    // type the DDL generator gives the column
+    pub fn cast_type(&self, col_type: &ColumnType) -> Option<String> {
+        match col_type {
+            ColumnType::Array(_) | ColumnType::Enum { .. }
+                if self.array_style == ArrayStyle::Native =>
+            {
+                Some(DdlConfig::postgres().sql_type(col_type))
+            }
+            _ => None,
+        }
+    }
 }
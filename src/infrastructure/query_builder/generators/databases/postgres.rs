@@ -13,6 +13,9 @@ impl PostgresGenerator {
             ColumnType::NaiveDateTime => Some("TIMESTAMP"),
             ColumnType::Json => Some("JSONB"),
             ColumnType::Binary => Some("BYTEA"),
+            ColumnType::Decimal => Some("NUMERIC"),
+            ColumnType::Uuid => Some("UUID"),
+            ColumnType::Interval => Some("INTERVAL"),
             _ => None,
         })
     }
@@ -12,6 +12,11 @@ impl SqliteGenerator {
             ColumnType::LongText => Some("TEXT"),
             ColumnType::Int => Some("INTEGER"),
             ColumnType::Float => Some("REAL"),
+            ColumnType::Decimal
+            | ColumnType::Uuid
+            | ColumnType::Date
+            | ColumnType::Time
+            | ColumnType::Interval => Some("TEXT"),
             _ => None,
         })
     }
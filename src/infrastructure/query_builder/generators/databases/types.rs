@@ -36,6 +36,26 @@ pub fn default_binary_type() -> &'static str {
     "BLOB"
 }
 
+pub fn default_decimal_type() -> &'static str {
+    "DECIMAL(38, 10)"
+}
+
+pub fn default_uuid_type() -> &'static str {
+    "CHAR(36)"
+}
+
+pub fn default_date_type() -> &'static str {
+    "DATE"
+}
+
+pub fn default_time_type() -> &'static str {
+    "TIME"
+}
+
+pub fn default_interval_type() -> &'static str {
+    "VARCHAR(64)"
+}
+
 pub fn build_type_map<F>(customize: F) -> impl Fn(&ColumnType) -> &'static str
 where
     F: Fn(&ColumnType) -> Option<&'static str>,
@@ -56,6 +76,13 @@ where
             ColumnType::NaiveDateTime => default_datetime_type(),
             ColumnType::Json => default_json_type(),
             ColumnType::Binary => default_binary_type(),
+            ColumnType::Decimal => default_decimal_type(),
+            ColumnType::Uuid => default_uuid_type(),
+            ColumnType::Date => default_date_type(),
+            ColumnType::Time => default_time_type(),
+            ColumnType::Interval => default_interval_type(),
+            ColumnType::Array(_) => default_json_type(),
+            ColumnType::Enum { .. } => default_text_type(),
         }
     }
 }
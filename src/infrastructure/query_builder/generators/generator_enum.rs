@@ -1,4 +1,5 @@
 use super::base::BaseGenerator;
+use super::config::{ArrayStyle, UpsertStyle};
 use super::sql_generator::SqlGenerator;
 use crate::domain::model::Entity;
 use crate::domain::query::builder::QueryBuilder;
@@ -119,6 +120,38 @@ impl SqlGenerator for SqlGeneratorEnum {
         self.get_generator().table_options()
     }
 
+    fn supports_ilike(&self) -> bool {
+        self.get_generator().supports_ilike()
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        self.get_generator().upsert_style()
+    }
+
+    fn supports_returning(&self) -> bool {
+        self.get_generator().supports_returning()
+    }
+
+    fn max_bind_params(&self) -> usize {
+        self.get_generator().max_bind_params()
+    }
+
+    fn last_insert_id_sql(&self) -> String {
+        self.get_generator().last_insert_id_sql()
+    }
+
+    fn supports_transactional_ddl(&self) -> bool {
+        self.get_generator().supports_transactional_ddl()
+    }
+
+    fn array_style(&self) -> ArrayStyle {
+        self.get_generator().array_style()
+    }
+
+    fn cast_type(&self, col_type: &ColumnType) -> Option<String> {
+        self.get_generator().cast_type(col_type)
+    }
+
     fn generate_create_table(&self, table: &TableDefinition) -> String {
         self.get_generator().generate_create_table(table)
     }
@@ -5,6 +5,6 @@ pub mod generator_enum;
 pub mod sql_generator;
 
 pub use base::BaseGenerator;
-pub use config::{GeneratorConfig, TypeMapper};
+pub use config::{ArrayStyle, GeneratorConfig, TypeMapper, UpsertStyle};
 pub use generator_enum::{MySqlGenerator, PostgresGenerator, SqlGeneratorEnum, SqliteGenerator};
 pub use sql_generator::SqlGenerator;
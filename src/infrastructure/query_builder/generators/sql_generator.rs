@@ -1,8 +1,10 @@
 use crate::domain::model::Entity;
+use crate::domain::query::assignment::Assignment;
 use crate::domain::query::builder::QueryBuilder;
 use crate::domain::value_objects::Value;
 use crate::infrastructure::query_builder::ast::*;
 use crate::infrastructure::query_builder::builders::QueryAstBuilder;
+use crate::infrastructure::query_builder::generators::{ArrayStyle, UpsertStyle};
 use crate::infrastructure::schema::{ColumnType, TableDefinition};
 
 pub trait SqlGenerator {
@@ -18,6 +20,38 @@ pub trait SqlGenerator {
         String::new()
     }
 
+    fn supports_ilike(&self) -> bool {
+        false
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnConflict
+    }
+
+    fn supports_returning(&self) -> bool {
+        false
+    }
+
+    fn max_bind_params(&self) -> usize {
+        65535
+    }
+
+    fn last_insert_id_sql(&self) -> String {
+        "SELECT LAST_INSERT_ID() AS last_insert_id".to_string()
+    }
+
+    fn supports_transactional_ddl(&self) -> bool {
+        false
+    }
+
+    fn array_style(&self) -> ArrayStyle {
+        ArrayStyle::Native
+    }
+
+    fn cast_type(&self, _col_type: &ColumnType) -> Option<String> {
+        None
+    }
+
     fn generate_select<E: Entity>(&self, builder: &QueryBuilder<E>) -> (String, Vec<Value>) {
         let ast = QueryAstBuilder::build_select(builder);
         self.generate_select_ast(&ast)
@@ -75,6 +109,35 @@ pub trait SqlGenerator {
         self.generate_insert_ast(&ast)
     }
 
+    fn generate_insert_many<E: Entity>(
+        &self,
+        entities: &[E],
+        returning: &[&str],
+    ) -> (String, Vec<Value>) {
+        let mut ast = QueryAstBuilder::build_insert_many(entities);
+        ast.returning = returning.iter().map(|c| c.to_string()).collect();
+        self.generate_insert_ast(&ast)
+    }
+
+    fn generate_upsert<E: Entity>(
+        &self,
+        entities: &[E],
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> (String, Vec<Value>) {
+        let ast = QueryAstBuilder::build_upsert(entities, conflict_columns, update_columns);
+        self.generate_insert_ast(&ast)
+    }
+
+    fn generate_insert_or_ignore<E: Entity>(&self, entity: &E) -> (String, Vec<Value>) {
+        let mut ast = QueryAstBuilder::build_insert(entity);
+        ast.on_conflict = Some(OnConflict {
+            columns: Vec::new(),
+            action: ConflictAction::DoNothing,
+        });
+        self.generate_insert_ast(&ast)
+    }
+
     fn generate_update<E: Entity>(&self, entity: &E) -> (String, Vec<Value>) {
         let ast = QueryAstBuilder::build_update(entity);
         self.generate_update_ast(&ast)
@@ -85,6 +148,20 @@ pub trait SqlGenerator {
         self.generate_delete_ast(&ast)
     }
 
+    fn generate_update_where<E: Entity>(
+        &self,
+        builder: &QueryBuilder<E>,
+        assignments: &[Assignment],
+    ) -> (String, Vec<Value>) {
+        let ast = QueryAstBuilder::build_update_where(builder, assignments);
+        self.generate_update_ast(&ast)
+    }
+
+    fn generate_delete_where<E: Entity>(&self, builder: &QueryBuilder<E>) -> (String, Vec<Value>) {
+        let ast = QueryAstBuilder::build_delete_where(builder);
+        self.generate_delete_ast(&ast)
+    }
+
     fn generate_create_table(&self, table: &TableDefinition) -> String {
         let mut sql = format!(
             "CREATE TABLE IF NOT EXISTS {} (",
@@ -1,6 +1,10 @@
 use crate::domain::model::Entity;
+use crate::domain::query::assignment::Assignment;
 use crate::domain::query::builder::{Operator, QueryBuilder};
-use crate::domain::repositories::{executor::QueryExecutor, Repository};
+use crate::domain::repositories::{
+    executor::{QueryExecutor, Transaction},
+    Repository,
+};
 use crate::domain::value_objects::Value;
 use crate::domain::TikalResult;
 use crate::infrastructure::query_builder::generators::{SqlGenerator, SqlGeneratorEnum};
@@ -48,6 +52,173 @@ where
         }
     }
 
+    // Bulk writes only honour filters, so anything else on the builder is refused
+    // rather than dropped, and touching every row has to be asked for.
+    fn bulk_write_error(query: &QueryBuilder<E>) -> Option<&'static str> {
+        if !query.joins.is_empty() || query.from_query.is_some() {
+            Some("joins and derived tables are not supported here, use where_exists or where_in_subquery instead")
+        } else if query.limit.is_some() || query.offset.is_some() {
+            Some("limit and offset are not supported here")
+        } else if !query.order_by.is_empty() {
+            Some("order_by is not supported here")
+        } else if !query.group_by.is_empty() || !query.having_filters.is_empty() {
+            Some("group_by and having are not supported here")
+        } else if !query.has_filters() && !query.unfiltered {
+            Some("no filters given, call unfiltered() to affect every row")
+        } else {
+            None
+        }
+    }
+
+    async fn insert_in_transaction(
+        &self,
+        entities: &[E],
+        collect_keys: bool,
+    ) -> TikalResult<(u64, Vec<Value>)> {
+        if entities.is_empty() {
+            return Ok((0, Vec::new()));
+        }
+
+        let mut tx = self.executor.begin_transaction().await?;
+        match self
+            .insert_chunks(tx.as_mut(), entities, collect_keys)
+            .await
+        {
+            Ok(result) => {
+                tx.commit().await?;
+                Ok(result)
+            }
+            Err(e) => {
+                if let Err(rollback_err) = tx.rollback().await {
+                    tracing::warn!("Failed to roll back batch insert: {}", rollback_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn insert_chunks(
+        &self,
+        tx: &mut dyn Transaction,
+        entities: &[E],
+        collect_keys: bool,
+    ) -> TikalResult<(u64, Vec<Value>)> {
+        let pk = E::primary_key();
+
+        let mut total = 0;
+        let mut keys = Vec::new();
+
+        for run in Self::key_runs(entities) {
+            let keyed = Self::has_key(&run[0]);
+            // Neither LAST_INSERT_ID() nor RETURNING ties the generated keys of a
+            // multi-row insert to its input rows, so those rows go one at a time
+            let generated_keys = collect_keys && !keyed;
+            let use_returning = generated_keys && self.generator.supports_returning();
+            let returning: &[&str] = if use_returning { &[pk] } else { &[] };
+            let rows_per_chunk = if generated_keys {
+                1
+            } else {
+                self.rows_per_chunk(run)
+            };
+
+            for chunk in run.chunks(rows_per_chunk) {
+                let (sql, params) = self.generator.generate_insert_many(chunk, returning);
+
+                if use_returning {
+                    let rows = tx.fetch_all(&sql, params).await?;
+                    total += rows.len() as u64;
+                    keys.extend(
+                        rows.into_iter()
+                            .map(|row| row.get(pk).cloned().unwrap_or(Value::Null)),
+                    );
+                    continue;
+                }
+
+                total += tx.execute(&sql, params).await?;
+                if !collect_keys {
+                    continue;
+                }
+                if keyed {
+                    keys.extend(
+                        chunk
+                            .iter()
+                            .map(|entity| entity.to_values().remove(pk).unwrap_or(Value::Null)),
+                    );
+                } else {
+                    keys.push(Value::Int(self.last_insert_id(tx).await?));
+                }
+            }
+        }
+
+        Ok((total, keys))
+    }
+
+    fn has_key(entity: &E) -> bool {
+        !matches!(
+            entity.to_values().get(E::primary_key()),
+            None | Some(Value::Null)
+        )
+    }
+
+    // Splits a batch into runs of rows that all have or all lack a key, so rows
+    // without one leave the key column out instead of sending an explicit NULL
+    fn key_runs(entities: &[E]) -> impl Iterator<Item = &[E]> {
+        entities.chunk_by(|a, b| Self::has_key(a) == Self::has_key(b))
+    }
+
+    // Keeps each multi-row statement under the dialect's bind parameter limit
+    fn rows_per_chunk(&self, entities: &[E]) -> usize {
+        let columns_per_row = entities[0].to_values().len().max(1);
+        (self.generator.max_bind_params() / columns_per_row).max(1)
+    }
+
+    async fn upsert_chunks(
+        &self,
+        tx: &mut dyn Transaction,
+        entities: &[E],
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> TikalResult<u64> {
+        let mut total = 0;
+        for run in Self::key_runs(entities) {
+            for chunk in run.chunks(self.rows_per_chunk(run)) {
+                let (sql, params) =
+                    self.generator
+                        .generate_upsert(chunk, conflict_columns, update_columns);
+                total += tx.execute(&sql, params).await?;
+            }
+        }
+        Ok(total)
+    }
+
+    async fn last_insert_id(&self, tx: &mut dyn Transaction) -> TikalResult<i64> {
+        let row = tx
+            .fetch_one(&self.generator.last_insert_id_sql(), Vec::new())
+            .await?;
+        match row.get("last_insert_id") {
+            Some(Value::Int(id)) => Ok(*id),
+            _ => Err(crate::domain::error::TikalError::mapping(
+                E::table_name(),
+                "could not read the generated primary key",
+            )),
+        }
+    }
+
+    async fn insert_and_reselect(&self, tx: &mut dyn Transaction, entity: &E) -> TikalResult<E> {
+        let pk = E::primary_key();
+        let (sql, params) = self.generator.generate_insert(entity);
+        tx.execute(&sql, params).await?;
+
+        let id = match entity.to_values().remove(pk) {
+            Some(Value::Null) | None => Value::Int(self.last_insert_id(tx).await?),
+            Some(id) => id,
+        };
+
+        let builder = E::find().where_clause(pk, Operator::Eq, id);
+        let (sql, params) = self.generator.generate_select(&builder);
+        E::from_row(tx.fetch_one(&sql, params).await?)
+    }
+
     async fn execute_aggregate_query<F>(
         &self,
         generate_sql: F,
@@ -137,6 +308,96 @@ where
         })
     }
 
+    async fn save_returning(&self, entity: &E) -> TikalResult<E> {
+        let context = || format!("Failed to save entity to table '{}'", E::table_name());
+
+        if self.generator.supports_returning() {
+            let (sql, params) = self
+                .generator
+                .generate_insert_many(std::slice::from_ref(entity), &["*"]);
+            let row = self
+                .executor
+                .fetch_one(&sql, params)
+                .await
+                .map_err(|e| e.with_context(context()))?;
+            return E::from_row(row);
+        }
+
+        let mut tx = self.executor.begin_transaction().await?;
+        match self.insert_and_reselect(tx.as_mut(), entity).await {
+            Ok(saved) => {
+                tx.commit().await?;
+                Ok(saved)
+            }
+            Err(e) => {
+                if let Err(rollback_err) = tx.rollback().await {
+                    tracing::warn!("Failed to roll back insert: {}", rollback_err);
+                }
+                Err(e.with_context(context()))
+            }
+        }
+    }
+
+    async fn upsert(
+        &self,
+        entity: &E,
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> TikalResult<u64> {
+        self.upsert_many(
+            std::slice::from_ref(entity),
+            conflict_columns,
+            update_columns,
+        )
+        .await
+    }
+
+    async fn upsert_many(
+        &self,
+        entities: &[E],
+        conflict_columns: &[&str],
+        update_columns: &[&str],
+    ) -> TikalResult<u64> {
+        if conflict_columns.is_empty() {
+            return Err(crate::domain::error::TikalError::validation(
+                "conflict_columns",
+                "upsert requires at least one conflict column",
+            ));
+        }
+        if entities.is_empty() {
+            return Ok(0);
+        }
+
+        let context = || format!("Failed to upsert entities into table '{}'", E::table_name());
+
+        let mut tx = self.executor.begin_transaction().await?;
+        match self
+            .upsert_chunks(tx.as_mut(), entities, conflict_columns, update_columns)
+            .await
+        {
+            Ok(total) => {
+                tx.commit().await?;
+                Ok(total)
+            }
+            Err(e) => {
+                if let Err(rollback_err) = tx.rollback().await {
+                    tracing::warn!("Failed to roll back batch upsert: {}", rollback_err);
+                }
+                Err(e.with_context(context()))
+            }
+        }
+    }
+
+    async fn insert_or_ignore(&self, entity: &E) -> TikalResult<u64> {
+        let (sql, params) = self.generator.generate_insert_or_ignore(entity);
+        self.executor.execute(&sql, params).await.map_err(|e| {
+            e.with_context(format!(
+                "Failed to insert entity into table '{}'",
+                E::table_name()
+            ))
+        })
+    }
+
     async fn update(&self, entity: &E) -> TikalResult<u64> {
         let values = entity.to_values();
         let pk = E::primary_key();
@@ -253,13 +514,31 @@ where
     }
 
     async fn save_many(&self, entities: &[E]) -> TikalResult<u64> {
-        let mut total = 0;
-        for entity in entities {
-            total += self.save(entity).await?;
-        }
+        let (total, _) = self
+            .insert_in_transaction(entities, false)
+            .await
+            .map_err(|e| {
+                e.with_context(format!(
+                    "Failed to save entities to table '{}'",
+                    E::table_name()
+                ))
+            })?;
         Ok(total)
     }
 
+    async fn save_many_returning_keys(&self, entities: &[E]) -> TikalResult<Vec<Value>> {
+        let (_, keys) = self
+            .insert_in_transaction(entities, true)
+            .await
+            .map_err(|e| {
+                e.with_context(format!(
+                    "Failed to save entities to table '{}'",
+                    E::table_name()
+                ))
+            })?;
+        Ok(keys)
+    }
+
     async fn update_many(&self, entities: &[E]) -> TikalResult<u64> {
         let mut total = 0;
         for entity in entities {
@@ -276,6 +555,50 @@ where
         Ok(total)
     }
 
+    async fn update_where(
+        &self,
+        query: QueryBuilder<E>,
+        assignments: Vec<Assignment>,
+    ) -> TikalResult<u64> {
+        if assignments.is_empty() {
+            return Err(crate::domain::error::TikalError::validation(
+                "assignments",
+                "update_where requires at least one assignment",
+            ));
+        }
+        if let Some(message) = Self::bulk_write_error(&query) {
+            return Err(crate::domain::error::TikalError::validation(
+                "update_where",
+                message,
+            ));
+        }
+
+        let (sql, params) = self.generator.generate_update_where(&query, &assignments);
+        self.executor.execute(&sql, params).await.map_err(|e| {
+            e.with_context(format!(
+                "Failed to update rows in table '{}'",
+                E::table_name()
+            ))
+        })
+    }
+
+    async fn delete_where(&self, query: QueryBuilder<E>) -> TikalResult<u64> {
+        if let Some(message) = Self::bulk_write_error(&query) {
+            return Err(crate::domain::error::TikalError::validation(
+                "delete_where",
+                message,
+            ));
+        }
+
+        let (sql, params) = self.generator.generate_delete_where(&query);
+        self.executor.execute(&sql, params).await.map_err(|e| {
+            e.with_context(format!(
+                "Failed to delete rows from table '{}'",
+                E::table_name()
+            ))
+        })
+    }
+
     async fn execute_raw(&self, sql: &str, params: Vec<Value>) -> TikalResult<u64> {
         self.executor.execute(sql, params).await
     }
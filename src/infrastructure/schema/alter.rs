@@ -0,0 +1,87 @@
+use super::generators::{DdlGenerator, UnifiedDdlGenerator};
+use super::introspector::SchemaIntrospector;
+use super::types::AlterTableDefinition;
+use crate::domain::repositories::executor::QueryExecutor;
+use crate::domain::repositories::types::DriverType;
+use crate::domain::value_objects::Value;
+use crate::domain::{TikalError, TikalResult};
+use crate::infrastructure::drivers::TransactionExecutor;
+
+pub async fn alter_table(
+    executor: &dyn QueryExecutor,
+    alter: &AlterTableDefinition,
+) -> TikalResult<()> {
+    let driver_info = executor.driver_info();
+    let ddl = UnifiedDdlGenerator::for_driver(&driver_info.driver_type);
+
+    let current = SchemaIntrospector::new(executor)
+        .introspect_table(&alter.table)
+        .await?;
+    if current.columns.is_empty() {
+        return Err(TikalError::validation(&alter.table, "Table does not exist"));
+    }
+
+    if driver_info.driver_type == DriverType::SQLite && ddl.requires_rebuild(alter) {
+        check_rebuild_is_safe(executor, &alter.table).await?;
+    }
+
+    let statements = ddl.generate_alter_table(alter, &current)?;
+
+    let tx = executor.begin_transaction().await?;
+    let tx = TransactionExecutor::new(tx, driver_info);
+    for statement in &statements {
+        if let Err(e) = tx.execute(statement, vec![]).await {
+            if let Err(rollback_err) = tx.rollback().await {
+                tracing::warn!(
+                    "Failed to roll back alteration of table '{}': {}",
+                    alter.table,
+                    rollback_err
+                );
+            }
+            return Err(e.with_context(format!("Altering table '{}'", alter.table)));
+        }
+    }
+    tx.commit().await
+}
+
+// Dropping the old table during a rebuild deletes its rows, which fires the
+// ON DELETE actions of every table referencing it while foreign keys are on.
+async fn check_rebuild_is_safe(executor: &dyn QueryExecutor, table: &str) -> TikalResult<()> {
+    let rows = executor.fetch_all("PRAGMA foreign_keys", vec![]).await?;
+    let enabled = rows
+        .first()
+        .and_then(|row| row.get("foreign_keys"))
+        .is_some_and(|value| matches!(value, Value::Int(1) | Value::Bool(true)));
+    if !enabled {
+        return Ok(());
+    }
+
+    let rows = executor
+        .fetch_all(
+            "SELECT m.name AS table_name FROM sqlite_master m \
+             JOIN pragma_foreign_key_list(m.name) f \
+             WHERE m.type = 'table' AND f.\"table\" = ? AND m.name <> f.\"table\" \
+             AND f.on_delete IN ('CASCADE', 'SET NULL', 'SET DEFAULT', 'RESTRICT')",
+            vec![Value::Text(table.to_string())],
+        )
+        .await?;
+    let mut referencing: Vec<String> = rows
+        .iter()
+        .filter_map(|row| match row.get("table_name") {
+            Some(Value::Text(name)) => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    if referencing.is_empty() {
+        return Ok(());
+    }
+
+    referencing.sort();
+    referencing.dedup();
+    Err(TikalError::invalid_state(&format!(
+        "Table '{}' must be rebuilt, which would trigger the ON DELETE actions of foreign keys in {}; \
+         alter it on a connection with foreign keys disabled",
+        table,
+        referencing.join(", ")
+    )))
+}
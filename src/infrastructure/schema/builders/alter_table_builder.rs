@@ -0,0 +1,165 @@
+use super::super::types::*;
+use super::index_builder::default_index_name;
+use crate::domain::value_objects::Value;
+
+pub struct AlterTableBuilder {
+    alter: AlterTableDefinition,
+}
+
+impl AlterTableBuilder {
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            alter: AlterTableDefinition {
+                table: table.into(),
+                operations: Vec::new(),
+            },
+        }
+    }
+
+    pub fn add_column(
+        self,
+        name: impl Into<String>,
+        column_type: ColumnType,
+    ) -> AlterColumnBuilder {
+        AlterColumnBuilder::new(self, name.into(), column_type)
+    }
+
+    pub fn drop_column(self, name: impl Into<String>) -> Self {
+        self.push(AlterOperation::DropColumn(name.into()))
+    }
+
+    pub fn rename_column(self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.push(AlterOperation::RenameColumn {
+            from: from.into(),
+            to: to.into(),
+        })
+    }
+
+    pub fn change_type(self, column: impl Into<String>, column_type: ColumnType) -> Self {
+        self.push(AlterOperation::ChangeType {
+            column: column.into(),
+            column_type,
+        })
+    }
+
+    pub fn nullable(self, column: impl Into<String>) -> Self {
+        self.push(AlterOperation::SetNullable {
+            column: column.into(),
+            nullable: true,
+        })
+    }
+
+    pub fn not_null(self, column: impl Into<String>) -> Self {
+        self.push(AlterOperation::SetNullable {
+            column: column.into(),
+            nullable: false,
+        })
+    }
+
+    pub fn set_default(self, column: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.push(AlterOperation::SetDefault {
+            column: column.into(),
+            default_value: Some(value.into()),
+        })
+    }
+
+    pub fn drop_default(self, column: impl Into<String>) -> Self {
+        self.push(AlterOperation::SetDefault {
+            column: column.into(),
+            default_value: None,
+        })
+    }
+
+    pub fn index(self, columns: Vec<String>) -> Self {
+        self.add_index(columns, false)
+    }
+
+    pub fn unique_index(self, columns: Vec<String>) -> Self {
+        self.add_index(columns, true)
+    }
+
+    pub fn drop_index(self, name: impl Into<String>) -> Self {
+        self.push(AlterOperation::DropIndex(name.into()))
+    }
+
+    pub fn add_foreign_key(self, foreign_key: ForeignKeyDefinition) -> Self {
+        self.push(AlterOperation::AddForeignKey(foreign_key))
+    }
+
+    pub fn drop_foreign_key(self, name: impl Into<String>) -> Self {
+        self.push(AlterOperation::DropForeignKey(name.into()))
+    }
+
+    pub fn rename_table(self, name: impl Into<String>) -> Self {
+        self.push(AlterOperation::RenameTable(name.into()))
+    }
+
+    pub fn build(self) -> AlterTableDefinition {
+        self.alter
+    }
+
+    fn add_index(self, columns: Vec<String>, unique: bool) -> Self {
+        let name = default_index_name(&self.alter.table, &columns, unique);
+        self.push(AlterOperation::AddIndex(IndexDefinition {
+            name,
+            columns,
+            unique,
+        }))
+    }
+
+    fn push(mut self, operation: AlterOperation) -> Self {
+        self.alter.operations.push(operation);
+        self
+    }
+}
+
+pub struct AlterColumnBuilder {
+    alter_builder: AlterTableBuilder,
+    column: ColumnDefinition,
+}
+
+impl AlterColumnBuilder {
+    fn new(alter_builder: AlterTableBuilder, name: String, column_type: ColumnType) -> Self {
+        Self {
+            alter_builder,
+            column: ColumnDefinition {
+                name,
+                column_type,
+                nullable: false,
+                primary_key: false,
+                auto_increment: false,
+                default_value: None,
+                unique: false,
+            },
+        }
+    }
+
+    pub fn nullable(mut self) -> Self {
+        self.column.nullable = true;
+        self
+    }
+
+    pub fn not_null(mut self) -> Self {
+        self.column.nullable = false;
+        self
+    }
+
+    pub fn unique(mut self) -> Self {
+        self.column.unique = true;
+        self
+    }
+
+    pub fn default_value(mut self, value: impl Into<Value>) -> Self {
+        self.column.default_value = Some(value.into());
+        self
+    }
+
+    pub fn default(self, value: impl Into<Value>) -> Self {
+        self.default_value(value)
+    }
+
+    pub fn finish(self) -> AlterTableBuilder {
+        self.alter_builder
+            .push(AlterOperation::AddColumn(self.column))
+    }
+}
@@ -0,0 +1,68 @@
+use super::super::types::*;
+use super::table_builder::TableBuilder;
+
+pub struct ForeignKeyBuilder {
+    table_builder: TableBuilder,
+    foreign_key: ForeignKeyDefinition,
+}
+
+impl ForeignKeyBuilder {
+    pub(super) fn new(
+        table_builder: TableBuilder,
+        columns: Vec<String>,
+        referenced_table: String,
+        referenced_columns: Vec<String>,
+    ) -> Self {
+        let name = default_foreign_key_name(&table_builder.table.name, &columns);
+        Self {
+            table_builder,
+            foreign_key: ForeignKeyDefinition {
+                name,
+                columns,
+                referenced_table,
+                referenced_columns,
+                on_delete: ReferentialAction::NoAction,
+                on_update: ReferentialAction::NoAction,
+            },
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.foreign_key.name = name.into();
+        self
+    }
+
+    pub fn on_delete(mut self, action: ReferentialAction) -> Self {
+        self.foreign_key.on_delete = action;
+        self
+    }
+
+    pub fn on_update(mut self, action: ReferentialAction) -> Self {
+        self.foreign_key.on_update = action;
+        self
+    }
+
+    pub fn cascade_on_delete(self) -> Self {
+        self.on_delete(ReferentialAction::Cascade)
+    }
+
+    pub fn null_on_delete(self) -> Self {
+        self.on_delete(ReferentialAction::SetNull)
+    }
+
+    pub fn restrict_on_delete(self) -> Self {
+        self.on_delete(ReferentialAction::Restrict)
+    }
+
+    pub fn cascade_on_update(self) -> Self {
+        self.on_update(ReferentialAction::Cascade)
+    }
+
+    pub fn finish(self) -> TableBuilder {
+        self.table_builder.add_foreign_key(self.foreign_key)
+    }
+}
+
+pub(super) fn default_foreign_key_name(table_name: &str, columns: &[String]) -> String {
+    format!("fk_{}_{}", table_name, columns.join("_"))
+}
@@ -25,11 +25,9 @@ impl IndexBuilder {
 
     pub fn finish(self) -> TableBuilder {
         let table_name = &self.table_builder.table.name;
-        let index_name = self.name.unwrap_or_else(|| {
-            let cols = self.columns.join("_");
-            let prefix = if self.unique { "unique" } else { "idx" };
-            format!("{}_{}_on_{}", prefix, table_name, cols)
-        });
+        let index_name = self
+            .name
+            .unwrap_or_else(|| default_index_name(table_name, &self.columns, self.unique));
 
         let index = IndexDefinition {
             name: index_name,
@@ -40,3 +38,8 @@ impl IndexBuilder {
         self.table_builder.add_index(index)
     }
 }
+
+pub(super) fn default_index_name(table_name: &str, columns: &[String], unique: bool) -> String {
+    let prefix = if unique { "unique" } else { "idx" };
+    format!("{}_{}_on_{}", prefix, table_name, columns.join("_"))
+}
@@ -1,7 +1,11 @@
+mod alter_table_builder;
 mod column_builder;
+mod foreign_key_builder;
 mod index_builder;
 mod table_builder;
 
+pub use alter_table_builder::{AlterColumnBuilder, AlterTableBuilder};
 pub use column_builder::ColumnBuilder;
+pub use foreign_key_builder::ForeignKeyBuilder;
 pub use index_builder::IndexBuilder;
 pub use table_builder::TableBuilder;
@@ -1,5 +1,6 @@
 use super::super::types::*;
 use super::column_builder::ColumnBuilder;
+use super::foreign_key_builder::ForeignKeyBuilder;
 use super::index_builder::IndexBuilder;
 
 pub struct TableBuilder {
@@ -13,6 +14,8 @@ impl TableBuilder {
                 name: name.into(),
                 columns: Vec::new(),
                 indexes: Vec::new(),
+                foreign_keys: Vec::new(),
+                unrepresented: Vec::new(),
             },
         }
     }
@@ -84,6 +87,20 @@ impl TableBuilder {
         self
     }
 
+    pub fn foreign(
+        self,
+        columns: Vec<String>,
+        referenced_table: impl Into<String>,
+        referenced_columns: Vec<String>,
+    ) -> ForeignKeyBuilder {
+        ForeignKeyBuilder::new(self, columns, referenced_table.into(), referenced_columns)
+    }
+
+    pub(super) fn add_foreign_key(mut self, foreign_key: ForeignKeyDefinition) -> Self {
+        self.table.foreign_keys.push(foreign_key);
+        self
+    }
+
     pub fn build(self) -> TableDefinition {
         self.table
     }
@@ -0,0 +1,165 @@
+use super::types::{ColumnDefinition, ColumnType, SchemaDefinition, TableDefinition};
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+pub fn generate_entities(schema: &SchemaDefinition) -> String {
+    let mut code = String::from("use tikal::prelude::*;\n");
+    for table in &schema.tables {
+        code.push('\n');
+        code.push_str(&generate_entity(table));
+    }
+    code
+}
+
+pub fn generate_entity(table: &TableDefinition) -> String {
+    let mut code = String::new();
+    let primary_keys: Vec<&ColumnDefinition> =
+        table.columns.iter().filter(|c| c.primary_key).collect();
+
+    code.push_str("#[derive(Entity, FromRow, Debug, Clone)]\n");
+    code.push_str(&format!("#[table_name = \"{}\"]\n", table.name));
+    match primary_keys.as_slice() {
+        [] => code.push_str("// The table has no primary key\n"),
+        [primary_key] => {
+            code.push_str(&format!("#[primary_key = \"{}\"]\n", primary_key.name));
+        }
+        [primary_key, ..] => {
+            let names: Vec<&str> = primary_keys.iter().map(|c| c.name.as_str()).collect();
+            code.push_str(&format!(
+                "// Composite primary key ({}), only the first column is used\n",
+                names.join(", ")
+            ));
+            code.push_str(&format!("#[primary_key = \"{}\"]\n", primary_key.name));
+        }
+    }
+    code.push_str(&format!(
+        "pub struct {} {{\n",
+        to_pascal_case(&singularize(&table.name))
+    ));
+
+    for column in &table.columns {
+        let field_name = field_name(&column.name);
+        if field_name != column.name {
+            code.push_str(&format!("    #[column_name = \"{}\"]\n", column.name));
+        }
+        if let Some(attribute) = enum_type_attribute(&column.column_type) {
+            code.push_str(&format!("    {}\n", attribute));
+        }
+        let rust_type = rust_type(&column.column_type);
+        let rust_type = if column.nullable && !column.primary_key {
+            format!("Option<{}>", rust_type)
+        } else {
+            rust_type
+        };
+        code.push_str(&format!("    pub {}: {},\n", field_name, rust_type));
+    }
+
+    code.push_str("}\n");
+    code
+}
+
+pub fn rust_type(column_type: &ColumnType) -> String {
+    let rust_type = match column_type {
+        ColumnType::Id => "i64",
+        ColumnType::LongText => "String",
+        // Vec<u8> is special-cased by the derive rather than listed
+        ColumnType::Binary => "Vec<u8>",
+        ColumnType::Array(inner) => return format!("Vec<{}>", rust_type(inner)),
+        // The field carries #[enum_type(...)], see enum_type_attribute
+        ColumnType::Enum { .. } => "String",
+        // The remaining variants carry no data, so their Debug form is the name.
+        // The derive's own table is read backwards, so generated fields map back
+        // to the same ColumnType when the entity is derived
+        column_type => {
+            let variant = format!("{:?}", column_type);
+            tikal_common::TYPE_MAPPINGS
+                .iter()
+                .find(|mapping| mapping.column_type == variant)
+                .map_or("String", |mapping| mapping.rust_type)
+        }
+    };
+    rust_type.to_string()
+}
+
+fn enum_type_attribute(column_type: &ColumnType) -> Option<String> {
+    match column_type {
+        ColumnType::Array(inner) => enum_type_attribute(inner),
+        ColumnType::Enum { name, variants } => {
+            let arguments: Vec<String> = std::iter::once(name)
+                .chain(variants)
+                .map(|s| format!("{:?}", s))
+                .collect();
+            Some(format!("#[enum_type({})]", arguments.join(", ")))
+        }
+        _ => None,
+    }
+}
+
+fn field_name(column_name: &str) -> String {
+    let name = to_snake_case(column_name);
+    let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    };
+    if RUST_KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            if c.is_uppercase() && previous_lower {
+                snake.push('_');
+            }
+            previous_lower = c.is_lowercase() || c.is_ascii_digit();
+            snake.extend(c.to_lowercase());
+        } else if !snake.ends_with('_') {
+            snake.push('_');
+            previous_lower = false;
+        }
+    }
+    snake.trim_matches('_').to_string()
+}
+
+fn to_pascal_case(name: &str) -> String {
+    to_snake_case(name)
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+// Reverses tikal_macros::utils::pluralize for the common English endings
+fn singularize(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if let Some(stem) = word.strip_suffix("ves") {
+        format!("{}f", stem)
+    } else if ["ches", "shes", "sses", "xes", "zes"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        word[..word.len() - 2].to_string()
+    } else if word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
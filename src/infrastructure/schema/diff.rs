@@ -0,0 +1,752 @@
+use super::generators::{reverse_alteration, DdlGenerator};
+use super::introspector::{column_type_from_sql, parse_default};
+use super::types::{
+    AlterOperation, AlterTableDefinition, ColumnDefinition, ColumnType, ForeignKeyDefinition,
+    IndexDefinition, SchemaDefinition, TableDefinition,
+};
+use crate::domain::value_objects::Value;
+use crate::domain::TikalResult;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub enum SchemaChange {
+    CreateTable(TableDefinition),
+    DropTable(TableDefinition),
+    AddColumn {
+        table: String,
+        column: ColumnDefinition,
+    },
+    DropColumn {
+        table: String,
+        column: ColumnDefinition,
+    },
+    CreateIndex {
+        table: String,
+        index: IndexDefinition,
+    },
+    DropIndex {
+        table: String,
+        index: IndexDefinition,
+    },
+    // `table` is the table as it stands before the alteration
+    AlterTable {
+        table: TableDefinition,
+        alter: AlterTableDefinition,
+    },
+}
+
+impl SchemaChange {
+    // The statements for the change, preceded by any types it needs such as
+    // PostgreSQL enums
+    pub fn statements(&self, ddl: &dyn DdlGenerator) -> TikalResult<Vec<String>> {
+        let statements = match self {
+            SchemaChange::CreateTable(table) => {
+                let mut statements = ddl.generate_create_types(&table.columns);
+                statements.push(ddl.generate_create_table(table));
+                statements
+            }
+            SchemaChange::DropTable(table) => vec![ddl.generate_drop_table(&table.name)],
+            SchemaChange::AddColumn { table, column } => {
+                let mut statements = ddl.generate_create_types(std::slice::from_ref(column));
+                statements.push(ddl.generate_add_column(table, column));
+                statements
+            }
+            SchemaChange::DropColumn { table, column } => {
+                vec![ddl.generate_drop_column(table, &column.name)]
+            }
+            SchemaChange::CreateIndex { table, index } => {
+                vec![ddl.generate_create_index(table, &index.name, &index.columns, index.unique)]
+            }
+            SchemaChange::DropIndex { index, .. } => vec![ddl.generate_drop_index(&index.name)],
+            SchemaChange::AlterTable { table, alter } => ddl.generate_alter_table(alter, table)?,
+        };
+        Ok(statements)
+    }
+
+    pub fn reverse(&self) -> SchemaChange {
+        match self.clone() {
+            SchemaChange::CreateTable(table) => SchemaChange::DropTable(table),
+            SchemaChange::DropTable(table) => SchemaChange::CreateTable(table),
+            SchemaChange::AddColumn { table, column } => SchemaChange::DropColumn { table, column },
+            SchemaChange::DropColumn { table, column } => SchemaChange::AddColumn { table, column },
+            SchemaChange::CreateIndex { table, index } => SchemaChange::DropIndex { table, index },
+            SchemaChange::DropIndex { table, index } => SchemaChange::CreateIndex { table, index },
+            SchemaChange::AlterTable { table, alter } => {
+                let (table, alter) = reverse_alteration(&table, &alter);
+                SchemaChange::AlterTable { table, alter }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn up_statements(&self, ddl: &dyn DdlGenerator) -> TikalResult<Vec<String>> {
+        let mut statements = Vec::new();
+        for change in &self.changes {
+            statements.extend(change.statements(ddl)?);
+        }
+        Ok(statements)
+    }
+
+    pub fn down_statements(&self, ddl: &dyn DdlGenerator) -> TikalResult<Vec<String>> {
+        let mut statements = Vec::new();
+        for change in self.changes.iter().rev() {
+            statements.extend(change.reverse().statements(ddl)?);
+        }
+        Ok(statements)
+    }
+
+    pub fn up_sql(&self, ddl: &dyn DdlGenerator) -> TikalResult<String> {
+        Ok(to_script(self.up_statements(ddl)?))
+    }
+
+    pub fn down_sql(&self, ddl: &dyn DdlGenerator) -> TikalResult<String> {
+        Ok(to_script(self.down_statements(ddl)?))
+    }
+
+    pub fn write_migration(
+        &self,
+        ddl: &dyn DdlGenerator,
+        dir: impl AsRef<Path>,
+        name: &str,
+    ) -> TikalResult<PathBuf> {
+        crate::infrastructure::migrations::sql::write_migration(
+            dir,
+            name,
+            &self.up_sql(ddl)?,
+            Some(&self.down_sql(ddl)?),
+        )
+    }
+}
+
+fn to_script(statements: Vec<String>) -> String {
+    statements
+        .iter()
+        .map(|s| format!("{};\n", s.trim_end().trim_end_matches(';')))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub struct SchemaDiffer {
+    drop_tables: bool,
+    drop_columns: bool,
+    drop_indexes: bool,
+    drop_foreign_keys: bool,
+    ignored_tables: Vec<String>,
+}
+
+impl SchemaDiffer {
+    pub fn new() -> Self {
+        Self {
+            drop_tables: false,
+            drop_columns: false,
+            drop_indexes: false,
+            drop_foreign_keys: false,
+            ignored_tables: vec!["__migrations".to_string(), "__migrations_lock".to_string()],
+        }
+    }
+
+    // Tables that exist only in the database are kept unless explicitly requested,
+    // since the entity list is rarely the complete schema.
+    pub fn drop_tables(mut self, drop: bool) -> Self {
+        self.drop_tables = drop;
+        self
+    }
+
+    // Columns missing from the entity are kept unless requested, dropping one
+    // loses its data.
+    pub fn drop_columns(mut self, drop: bool) -> Self {
+        self.drop_columns = drop;
+        self
+    }
+
+    // Databases create indexes of their own for UNIQUE constraints, so unknown
+    // indexes are kept by default.
+    pub fn drop_indexes(mut self, drop: bool) -> Self {
+        self.drop_indexes = drop;
+        self
+    }
+
+    // Foreign keys the entities do not declare are kept unless requested; ones
+    // whose definition changed are always replaced.
+    pub fn drop_foreign_keys(mut self, drop: bool) -> Self {
+        self.drop_foreign_keys = drop;
+        self
+    }
+
+    pub fn ignore_table(mut self, name: impl Into<String>) -> Self {
+        self.ignored_tables.push(name.into());
+        self
+    }
+
+    pub fn diff(
+        &self,
+        ddl: &dyn DdlGenerator,
+        current: &SchemaDefinition,
+        target: &[TableDefinition],
+    ) -> SchemaDiff {
+        let mut changes = Vec::new();
+        let mut created = Vec::new();
+
+        for table in target {
+            match current.tables.iter().find(|t| t.name == table.name) {
+                Some(existing) => self.diff_table(ddl, existing, table, &mut changes),
+                None => created.push(table.clone()),
+            }
+        }
+
+        for table in in_dependency_order(created) {
+            changes.push(SchemaChange::CreateTable(table.clone()));
+            changes.extend(table.indexes.iter().map(|index| SchemaChange::CreateIndex {
+                table: table.name.clone(),
+                index: index.clone(),
+            }));
+        }
+
+        if self.drop_tables {
+            let dropped: Vec<TableDefinition> = current
+                .tables
+                .iter()
+                .filter(|table| {
+                    !self.ignored_tables.contains(&table.name)
+                        && !target.iter().any(|t| t.name == table.name)
+                })
+                .cloned()
+                .collect();
+            // Referencing tables go first
+            for table in in_dependency_order(dropped).into_iter().rev() {
+                changes.extend(table.indexes.iter().map(|index| SchemaChange::DropIndex {
+                    table: table.name.clone(),
+                    index: index.clone(),
+                }));
+                changes.push(SchemaChange::DropTable(table));
+            }
+        }
+
+        SchemaDiff { changes }
+    }
+
+    fn diff_table(
+        &self,
+        ddl: &dyn DdlGenerator,
+        current: &TableDefinition,
+        target: &TableDefinition,
+        changes: &mut Vec<SchemaChange>,
+    ) {
+        // Alterations restate the table on some databases, so it has to
+        // include the columns added before them
+        let mut altered = current.clone();
+        for column in &target.columns {
+            if !current.columns.iter().any(|c| c.name == column.name) {
+                altered.columns.push(column.clone());
+                changes.push(SchemaChange::AddColumn {
+                    table: target.name.clone(),
+                    column: column.clone(),
+                });
+            }
+        }
+
+        let operations = self.alter_operations(ddl, current, target);
+        if !operations.is_empty() {
+            changes.push(SchemaChange::AlterTable {
+                table: altered,
+                alter: AlterTableDefinition {
+                    table: target.name.clone(),
+                    operations,
+                },
+            });
+        }
+
+        for index in &target.indexes {
+            if !current.indexes.iter().any(|i| i.name == index.name) {
+                changes.push(SchemaChange::CreateIndex {
+                    table: target.name.clone(),
+                    index: index.clone(),
+                });
+            }
+        }
+
+        if self.drop_indexes {
+            for index in &current.indexes {
+                if !target.indexes.iter().any(|i| i.name == index.name) {
+                    changes.push(SchemaChange::DropIndex {
+                        table: target.name.clone(),
+                        index: index.clone(),
+                    });
+                }
+            }
+        }
+
+        if self.drop_columns {
+            for column in &current.columns {
+                if !target.columns.iter().any(|c| c.name == column.name) {
+                    changes.push(SchemaChange::DropColumn {
+                        table: target.name.clone(),
+                        column: column.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    // Foreign keys are dropped first and added last, so the columns they
+    // cover can change in between
+    fn alter_operations(
+        &self,
+        ddl: &dyn DdlGenerator,
+        current: &TableDefinition,
+        target: &TableDefinition,
+    ) -> Vec<AlterOperation> {
+        let mut dropped = Vec::new();
+        let mut added = Vec::new();
+        for foreign_key in &target.foreign_keys {
+            if current
+                .foreign_keys
+                .iter()
+                .any(|fk| same_foreign_key(fk, foreign_key))
+            {
+                continue;
+            }
+            if current
+                .foreign_keys
+                .iter()
+                .any(|fk| fk.name == foreign_key.name)
+            {
+                dropped.push(AlterOperation::DropForeignKey(foreign_key.name.clone()));
+            }
+            added.push(AlterOperation::AddForeignKey(foreign_key.clone()));
+        }
+        if self.drop_foreign_keys {
+            for foreign_key in &current.foreign_keys {
+                if !target
+                    .foreign_keys
+                    .iter()
+                    .any(|fk| fk.name == foreign_key.name || same_foreign_key(fk, foreign_key))
+                {
+                    dropped.push(AlterOperation::DropForeignKey(foreign_key.name.clone()));
+                }
+            }
+        }
+
+        let mut operations = dropped;
+        for column in &target.columns {
+            let Some(existing) = current.columns.iter().find(|c| c.name == column.name) else {
+                continue;
+            };
+            if !same_type(ddl, &existing.column_type, &column.column_type) {
+                operations.push(AlterOperation::ChangeType {
+                    column: column.name.clone(),
+                    column_type: column.column_type.clone(),
+                });
+            }
+            // Primary keys are NOT NULL whatever the entity's field type says
+            if !column.primary_key && existing.nullable != column.nullable {
+                operations.push(AlterOperation::SetNullable {
+                    column: column.name.clone(),
+                    nullable: column.nullable,
+                });
+            }
+            if !same_default(
+                ddl,
+                &column.column_type,
+                &existing.default_value,
+                &column.default_value,
+            ) {
+                operations.push(AlterOperation::SetDefault {
+                    column: column.name.clone(),
+                    default_value: column.default_value.clone(),
+                });
+            }
+        }
+        operations.extend(added);
+        operations
+    }
+}
+
+// Introspection maps database types back onto the closest column type, so both
+// sides are compared as introspection would report them. Enums are compared as
+// written, the enum style decides whether the database can tell them apart.
+fn same_type(ddl: &dyn DdlGenerator, current: &ColumnType, target: &ColumnType) -> bool {
+    let introspected =
+        |column_type: &ColumnType| column_type_from_sql(&ddl.generate_column_type(column_type));
+    match (current, target) {
+        (ColumnType::Array(current), ColumnType::Array(target)) => same_type(ddl, current, target),
+        (
+            ColumnType::Enum {
+                variants: current_variants,
+                ..
+            },
+            ColumnType::Enum {
+                variants: target_variants,
+                ..
+            },
+        ) => {
+            current_variants == target_variants
+                && ddl.generate_column_type(current) == ddl.generate_column_type(target)
+        }
+        (ColumnType::Enum { .. }, _) | (_, ColumnType::Enum { .. }) => {
+            ddl.generate_column_type(current) == ddl.generate_column_type(target)
+        }
+        _ => {
+            ddl.generate_column_type(&introspected(current))
+                == ddl.generate_column_type(&introspected(target))
+        }
+    }
+}
+
+// Defaults are compared as introspection reads them back for the column's
+// type, so a Bool default on a SQLite INTEGER column matches the Int it
+// introspects as
+fn same_default(
+    ddl: &dyn DdlGenerator,
+    column_type: &ColumnType,
+    current: &Option<Value>,
+    target: &Option<Value>,
+) -> bool {
+    let introspected = column_type_from_sql(&ddl.generate_column_type(column_type));
+    let normalize = |value: &Option<Value>| {
+        value.as_ref().map(|value| {
+            parse_default(&value.to_string(), &introspected).unwrap_or_else(|| value.clone())
+        })
+    };
+    normalize(current) == normalize(target)
+}
+
+// Referenced tables come first so each table can be created after the ones its
+// foreign keys point at; cycles keep their original order.
+pub fn in_dependency_order(mut pending: Vec<TableDefinition>) -> Vec<TableDefinition> {
+    let mut ordered: Vec<TableDefinition> = Vec::new();
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|table| {
+            table.foreign_keys.iter().all(|fk| {
+                fk.referenced_table == table.name
+                    || !pending.iter().any(|t| t.name == fk.referenced_table)
+            })
+        });
+        ordered.push(pending.remove(ready.unwrap_or(0)));
+    }
+    ordered
+}
+
+fn same_foreign_key(a: &ForeignKeyDefinition, b: &ForeignKeyDefinition) -> bool {
+    a.columns == b.columns
+        && a.referenced_table == b.referenced_table
+        && a.referenced_columns == b.referenced_columns
+        && a.on_delete == b.on_delete
+        && a.on_update == b.on_update
+}
+
+impl Default for SchemaDiffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::schema::builders::TableBuilder;
+    use crate::infrastructure::schema::generators::UnifiedDdlGenerator;
+
+    fn mood(variants: &[&str]) -> ColumnType {
+        ColumnType::Enum {
+            name: "mood".to_string(),
+            variants: variants.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    fn operations(diff: &SchemaDiff) -> Vec<AlterOperation> {
+        diff.changes
+            .iter()
+            .flat_map(|change| match change {
+                SchemaChange::AlterTable { alter, .. } => alter.operations.clone(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn introspected_sqlite_table_matches_its_definition() {
+        let target = TableBuilder::new("things")
+            .id()
+            .column("title", ColumnType::Text)
+            .default("it's")
+            .finish()
+            .column("active", ColumnType::Bool)
+            .default(true)
+            .finish()
+            .column("price", ColumnType::Decimal)
+            .default(Value::Decimal("9.99".parse().unwrap()))
+            .finish()
+            .column("body", ColumnType::LongText)
+            .nullable()
+            .finish()
+            .column("mood", mood(&["happy", "sad"]))
+            .finish()
+            .build();
+        // What SQLite reports back for the table Tikal created from `target`
+        let current = TableBuilder::new("things")
+            .column("id", ColumnType::BigInt)
+            .primary_key()
+            .auto_increment()
+            .finish()
+            .column("title", ColumnType::Text)
+            .default("it's")
+            .finish()
+            .column("active", ColumnType::Int)
+            .default(Value::Int(1))
+            .finish()
+            .column("price", ColumnType::Text)
+            .default("9.99")
+            .finish()
+            .column("body", ColumnType::Text)
+            .nullable()
+            .finish()
+            .column("mood", mood(&["happy", "sad"]))
+            .finish()
+            .build();
+
+        let diff = SchemaDiffer::new().diff(
+            &UnifiedDdlGenerator::sqlite(),
+            &SchemaDefinition {
+                tables: vec![current],
+            },
+            &[target],
+        );
+        assert!(diff.is_empty(), "unexpected changes: {:?}", diff.changes);
+    }
+
+    #[test]
+    fn introspected_postgres_table_matches_its_definition() {
+        let target = TableBuilder::new("things")
+            .id()
+            .column("count", ColumnType::Int)
+            .default(Value::Int(0))
+            .finish()
+            .column("ratio", ColumnType::Float)
+            .default(Value::Float(1.5.into()))
+            .finish()
+            .column("tags", ColumnType::Array(Box::new(ColumnType::Text)))
+            .nullable()
+            .finish()
+            .build();
+        let current = TableBuilder::new("things")
+            .column("id", ColumnType::BigInt)
+            .primary_key()
+            .auto_increment()
+            .finish()
+            .column("count", ColumnType::Int)
+            .default(Value::Int(0))
+            .finish()
+            .column("ratio", ColumnType::Float)
+            .default(Value::Float(1.5.into()))
+            .finish()
+            .column("tags", ColumnType::Array(Box::new(ColumnType::Text)))
+            .nullable()
+            .finish()
+            .build();
+
+        let diff = SchemaDiffer::new().diff(
+            &UnifiedDdlGenerator::postgres(),
+            &SchemaDefinition {
+                tables: vec![current],
+            },
+            &[target],
+        );
+        assert!(diff.is_empty(), "unexpected changes: {:?}", diff.changes);
+    }
+
+    #[test]
+    fn changed_columns_are_altered() {
+        let current = TableBuilder::new("things")
+            .id()
+            .column("count", ColumnType::Int)
+            .default(Value::Int(0))
+            .finish()
+            .column("note", ColumnType::Text)
+            .finish()
+            .column("mood", mood(&["happy"]))
+            .finish()
+            .build();
+        let target = TableBuilder::new("things")
+            .id()
+            .column("count", ColumnType::BigInt)
+            .default(Value::Int(1))
+            .finish()
+            .column("note", ColumnType::Text)
+            .nullable()
+            .finish()
+            .column("mood", mood(&["happy", "sad"]))
+            .finish()
+            .build();
+
+        let diff = SchemaDiffer::new().diff(
+            &UnifiedDdlGenerator::postgres(),
+            &SchemaDefinition {
+                tables: vec![current],
+            },
+            &[target],
+        );
+        let operations = operations(&diff);
+        assert_eq!(operations.len(), 4, "{:?}", operations);
+        assert!(matches!(
+            &operations[0],
+            AlterOperation::ChangeType { column, column_type: ColumnType::BigInt } if column == "count"
+        ));
+        assert!(matches!(
+            &operations[1],
+            AlterOperation::SetDefault { column, default_value: Some(Value::Int(1)) } if column == "count"
+        ));
+        assert!(matches!(
+            &operations[2],
+            AlterOperation::SetNullable { column, nullable: true } if column == "note"
+        ));
+        assert!(matches!(
+            &operations[3],
+            AlterOperation::ChangeType { column, .. } if column == "mood"
+        ));
+    }
+
+    #[test]
+    fn changed_foreign_keys_are_dropped_first_and_added_last() {
+        let current = TableBuilder::new("comments")
+            .id()
+            .column("post_id", ColumnType::Int)
+            .finish()
+            .foreign(vec!["post_id".into()], "posts", vec!["id".into()])
+            .finish()
+            .build();
+        let target = TableBuilder::new("comments")
+            .id()
+            .column("post_id", ColumnType::BigInt)
+            .finish()
+            .foreign(vec!["post_id".into()], "posts", vec!["id".into()])
+            .cascade_on_delete()
+            .finish()
+            .build();
+
+        let diff = SchemaDiffer::new().diff(
+            &UnifiedDdlGenerator::postgres(),
+            &SchemaDefinition {
+                tables: vec![current],
+            },
+            &[target],
+        );
+        let operations = operations(&diff);
+        assert!(matches!(
+            operations.as_slice(),
+            [
+                AlterOperation::DropForeignKey(_),
+                AlterOperation::ChangeType { .. },
+                AlterOperation::AddForeignKey(_),
+            ]
+        ));
+    }
+
+    #[test]
+    fn unknown_columns_and_foreign_keys_are_kept_by_default() {
+        let current = TableBuilder::new("comments")
+            .id()
+            .column("legacy", ColumnType::Text)
+            .finish()
+            .foreign(vec!["legacy".into()], "others", vec!["id".into()])
+            .finish()
+            .build();
+        let target = TableBuilder::new("comments").id().build();
+        let schema = SchemaDefinition {
+            tables: vec![current],
+        };
+        let ddl = UnifiedDdlGenerator::postgres();
+
+        assert!(SchemaDiffer::new()
+            .diff(&ddl, &schema, std::slice::from_ref(&target))
+            .is_empty());
+
+        let diff = SchemaDiffer::new()
+            .drop_columns(true)
+            .drop_foreign_keys(true)
+            .diff(&ddl, &schema, &[target]);
+        assert!(matches!(
+            diff.changes.as_slice(),
+            [
+                SchemaChange::AlterTable { .. },
+                SchemaChange::DropColumn { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn referenced_tables_come_first() {
+        let comments = TableBuilder::new("comments")
+            .id()
+            .column("post_id", ColumnType::BigInt)
+            .finish()
+            .foreign(vec!["post_id".into()], "posts", vec!["id".into()])
+            .finish()
+            .build();
+        let posts = TableBuilder::new("posts")
+            .id()
+            .column("parent_id", ColumnType::BigInt)
+            .nullable()
+            .finish()
+            .foreign(vec!["parent_id".into()], "posts", vec!["id".into()])
+            .finish()
+            .build();
+        let users = TableBuilder::new("users").id().build();
+
+        let ordered: Vec<String> = in_dependency_order(vec![comments, posts, users])
+            .into_iter()
+            .map(|table| table.name)
+            .collect();
+        assert_eq!(ordered, ["posts", "comments", "users"]);
+    }
+
+    #[test]
+    fn down_statements_reverse_the_up_statements() {
+        let current = TableBuilder::new("things")
+            .id()
+            .column("count", ColumnType::Int)
+            .finish()
+            .build();
+        let target = TableBuilder::new("things")
+            .id()
+            .column("count", ColumnType::BigInt)
+            .finish()
+            .column("title", ColumnType::Text)
+            .nullable()
+            .finish()
+            .build();
+        let ddl = UnifiedDdlGenerator::postgres();
+
+        let diff = SchemaDiffer::new().diff(
+            &ddl,
+            &SchemaDefinition {
+                tables: vec![current],
+            },
+            &[target],
+        );
+        assert_eq!(
+            diff.up_statements(&ddl).unwrap(),
+            [
+                "ALTER TABLE \"things\" ADD COLUMN \"title\" TEXT",
+                "ALTER TABLE \"things\" ALTER COLUMN \"count\" TYPE BIGINT USING \"count\"::BIGINT",
+            ]
+        );
+        assert_eq!(
+            diff.down_statements(&ddl).unwrap(),
+            [
+                "ALTER TABLE \"things\" ALTER COLUMN \"count\" TYPE INTEGER USING \"count\"::INTEGER",
+                "ALTER TABLE \"things\" DROP COLUMN \"title\"",
+            ]
+        );
+    }
+}
@@ -0,0 +1,581 @@
+use super::super::types::{
+    AlterOperation, AlterTableDefinition, ColumnDefinition, TableDefinition, UnrepresentedState,
+};
+use super::config::AlterStyle;
+use super::r#trait::DdlGenerator;
+use super::unified::UnifiedDdlGenerator;
+use crate::domain::{TikalError, TikalResult};
+
+impl UnifiedDdlGenerator {
+    pub fn requires_rebuild(&self, alter: &AlterTableDefinition) -> bool {
+        self.config().alter_style == AlterStyle::Rebuild
+            && alter.operations.iter().any(|operation| match operation {
+                AlterOperation::RenameColumn { .. }
+                | AlterOperation::RenameTable(_)
+                | AlterOperation::AddIndex(_)
+                | AlterOperation::DropIndex(_) => false,
+                AlterOperation::AddColumn(column) => {
+                    column.primary_key
+                        || column.unique
+                        || (!column.nullable && column.default_value.is_none())
+                }
+                _ => true,
+            })
+    }
+
+    pub(super) fn alter_table_statements(
+        &self,
+        alter: &AlterTableDefinition,
+        current: &TableDefinition,
+    ) -> TikalResult<Vec<String>> {
+        if self.requires_rebuild(alter) {
+            return self.rebuild_table(alter, current);
+        }
+
+        let mut table = current.clone();
+        let mut statements = Vec::new();
+        for operation in &alter.operations {
+            let table_name = table.name.clone();
+            apply_operation(&mut table, operation)?;
+            // A column that becomes an enum needs its type to exist first
+            if let AlterOperation::AddColumn(ColumnDefinition { name: column, .. })
+            | AlterOperation::ChangeType { column, .. } = operation
+                && let Some(definition) = table.columns.iter().find(|c| c.name == *column)
+            {
+                statements.extend(self.generate_create_types(std::slice::from_ref(definition)));
+            }
+            statements.push(self.alter_operation_sql(&table_name, operation, &table)?);
+        }
+        Ok(statements)
+    }
+
+    fn alter_operation_sql(
+        &self,
+        table_name: &str,
+        operation: &AlterOperation,
+        table: &TableDefinition,
+    ) -> TikalResult<String> {
+        let quoted_table = self.quote_identifier(table_name);
+        let modify = self.config().alter_style == AlterStyle::Modify;
+
+        let sql = match operation {
+            AlterOperation::AddColumn(column) => self.generate_add_column(table_name, column),
+            AlterOperation::DropColumn(name) => self.generate_drop_column(table_name, name),
+            AlterOperation::RenameColumn { from, to } => format!(
+                "ALTER TABLE {} RENAME COLUMN {} TO {}",
+                quoted_table,
+                self.quote_identifier(from),
+                self.quote_identifier(to)
+            ),
+            AlterOperation::ChangeType { column, .. }
+            | AlterOperation::SetNullable { column, .. }
+            | AlterOperation::SetDefault { column, .. }
+                if modify =>
+            {
+                // MODIFY restates the whole column, so start from its current state
+                check_restatable(
+                    table,
+                    table
+                        .unrepresented
+                        .iter()
+                        .filter(|state| state.column() == Some(column.as_str())),
+                )?;
+                let mut definition = table
+                    .columns
+                    .iter()
+                    .find(|c| c.name == *column)
+                    .cloned()
+                    .expect("column was validated by apply_operation");
+                definition.primary_key = false;
+                format!(
+                    "ALTER TABLE {} MODIFY COLUMN {}",
+                    quoted_table,
+                    self.generate_column_definition(&definition)
+                )
+            }
+            AlterOperation::ChangeType {
+                column,
+                column_type,
+            } => {
+                let column = self.quote_identifier(column);
+                let sql_type = self.config().sql_type(column_type);
+                format!(
+                    "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::{}",
+                    quoted_table, column, sql_type, column, sql_type
+                )
+            }
+            AlterOperation::SetNullable { column, nullable } => format!(
+                "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL",
+                quoted_table,
+                self.quote_identifier(column),
+                if *nullable { "DROP" } else { "SET" }
+            ),
+            AlterOperation::SetDefault {
+                column,
+                default_value,
+            } => match default_value {
+                Some(value) => format!(
+                    "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {}",
+                    quoted_table,
+                    self.quote_identifier(column),
+                    value
+                ),
+                None => format!(
+                    "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT",
+                    quoted_table,
+                    self.quote_identifier(column)
+                ),
+            },
+            AlterOperation::AddIndex(index) => {
+                self.generate_create_index(table_name, &index.name, &index.columns, index.unique)
+            }
+            AlterOperation::DropIndex(name) if modify => format!(
+                "ALTER TABLE {} DROP INDEX {}",
+                quoted_table,
+                self.quote_identifier(name)
+            ),
+            AlterOperation::DropIndex(name) => self.generate_drop_index(name),
+            AlterOperation::AddForeignKey(foreign_key) => format!(
+                "ALTER TABLE {} ADD {}",
+                quoted_table,
+                self.generate_foreign_key(foreign_key)
+            ),
+            AlterOperation::DropForeignKey(name) => format!(
+                "ALTER TABLE {} DROP {} {}",
+                quoted_table,
+                if modify { "FOREIGN KEY" } else { "CONSTRAINT" },
+                self.quote_identifier(name)
+            ),
+            AlterOperation::RenameTable(name) => format!(
+                "ALTER TABLE {} RENAME TO {}",
+                quoted_table,
+                self.quote_identifier(name)
+            ),
+        };
+        Ok(sql)
+    }
+
+    // SQLite cannot alter most column properties in place, so the table is
+    // recreated with the new definition and its rows copied across.
+    fn rebuild_table(
+        &self,
+        alter: &AlterTableDefinition,
+        current: &TableDefinition,
+    ) -> TikalResult<Vec<String>> {
+        let mut table = current.clone();
+        // Pairs each column of the new table with the old column its data comes from
+        let mut sources: Vec<(String, Option<String>)> = current
+            .columns
+            .iter()
+            .map(|c| (c.name.clone(), Some(c.name.clone())))
+            .collect();
+
+        for operation in &alter.operations {
+            apply_operation(&mut table, operation)?;
+            match operation {
+                AlterOperation::AddColumn(column) => sources.push((column.name.clone(), None)),
+                AlterOperation::DropColumn(name) => sources.retain(|(column, _)| column != name),
+                AlterOperation::RenameColumn { from, to } => {
+                    for (column, _) in sources.iter_mut().filter(|(column, _)| column == from) {
+                        *column = to.clone();
+                    }
+                }
+                _ => {}
+            }
+        }
+        check_restatable(current, table.unrepresented.iter())?;
+
+        let backup_table = self.quote_identifier(&format!("__tikal_old_{}", current.name));
+        let (targets, sources): (Vec<String>, Vec<String>) = sources
+            .into_iter()
+            .filter_map(|(target, source)| {
+                Some((
+                    self.quote_identifier(&target),
+                    self.quote_identifier(&source?),
+                ))
+            })
+            .unzip();
+
+        // The new table is created under its final name rather than renamed into
+        // place: with foreign keys enabled, dropping the old table counts every
+        // referencing row as a violation, and only inserting the parent rows again
+        // clears those before commit.
+        let mut statements = vec![
+            "PRAGMA defer_foreign_keys = ON".to_string(),
+            format!(
+                "CREATE TABLE {} AS SELECT * FROM {}",
+                backup_table,
+                self.quote_identifier(&current.name)
+            ),
+            format!("DROP TABLE {}", self.quote_identifier(&current.name)),
+            self.generate_create_table(&TableDefinition {
+                indexes: Vec::new(),
+                ..table.clone()
+            }),
+            format!(
+                "INSERT INTO {} ({}) SELECT {} FROM {}",
+                self.quote_identifier(&table.name),
+                targets.join(", "),
+                sources.join(", "),
+                backup_table
+            ),
+            format!("DROP TABLE {}", backup_table),
+        ];
+
+        for index in &table.indexes {
+            // Names of automatic indexes are reserved by SQLite
+            let name = if index.name.starts_with("sqlite_") {
+                format!("unique_{}_on_{}", table.name, index.columns.join("_"))
+            } else {
+                index.name.clone()
+            };
+            statements.push(self.generate_create_index(
+                &table.name,
+                &name,
+                &index.columns,
+                index.unique,
+            ));
+        }
+
+        Ok(statements)
+    }
+}
+
+// Restating state the definition cannot express would silently drop it
+fn check_restatable<'a>(
+    table: &TableDefinition,
+    states: impl Iterator<Item = &'a UnrepresentedState>,
+) -> TikalResult<()> {
+    let lost: Vec<String> = states.map(|state| state.to_string()).collect();
+    if lost.is_empty() {
+        return Ok(());
+    }
+    Err(TikalError::invalid_state(&format!(
+        "Altering table '{}' restates its definition, which would lose {}; \
+         alter it with raw SQL instead",
+        table.name,
+        lost.join(", ")
+    )))
+}
+
+// The operations undoing `alter`, along with the table as `alter` leaves it.
+// Invalid operations are skipped, they fail when the statements are generated.
+pub(crate) fn reverse_alteration(
+    current: &TableDefinition,
+    alter: &AlterTableDefinition,
+) -> (TableDefinition, AlterTableDefinition) {
+    let mut table = current.clone();
+    let mut operations = Vec::new();
+    for operation in &alter.operations {
+        let inverse = inverse_operation(&table, operation);
+        if apply_operation(&mut table, operation).is_ok() {
+            operations.extend(inverse);
+        }
+    }
+    operations.reverse();
+    let alter = AlterTableDefinition {
+        table: table.name.clone(),
+        operations,
+    };
+    (table, alter)
+}
+
+fn inverse_operation(
+    table: &TableDefinition,
+    operation: &AlterOperation,
+) -> Option<AlterOperation> {
+    let column = |name: &str| table.columns.iter().find(|c| c.name == name);
+    let inverse = match operation {
+        AlterOperation::AddColumn(column) => AlterOperation::DropColumn(column.name.clone()),
+        AlterOperation::DropColumn(name) => AlterOperation::AddColumn(column(name)?.clone()),
+        AlterOperation::RenameColumn { from, to } => AlterOperation::RenameColumn {
+            from: to.clone(),
+            to: from.clone(),
+        },
+        AlterOperation::ChangeType { column: name, .. } => AlterOperation::ChangeType {
+            column: name.clone(),
+            column_type: column(name)?.column_type.clone(),
+        },
+        AlterOperation::SetNullable { column: name, .. } => AlterOperation::SetNullable {
+            column: name.clone(),
+            nullable: column(name)?.nullable,
+        },
+        AlterOperation::SetDefault { column: name, .. } => AlterOperation::SetDefault {
+            column: name.clone(),
+            default_value: column(name)?.default_value.clone(),
+        },
+        AlterOperation::AddIndex(index) => AlterOperation::DropIndex(index.name.clone()),
+        AlterOperation::DropIndex(name) => {
+            AlterOperation::AddIndex(table.indexes.iter().find(|i| i.name == *name)?.clone())
+        }
+        AlterOperation::AddForeignKey(foreign_key) => {
+            AlterOperation::DropForeignKey(foreign_key.name.clone())
+        }
+        AlterOperation::DropForeignKey(name) => AlterOperation::AddForeignKey(
+            table
+                .foreign_keys
+                .iter()
+                .find(|fk| fk.name == *name)?
+                .clone(),
+        ),
+        AlterOperation::RenameTable(_) => AlterOperation::RenameTable(table.name.clone()),
+    };
+    Some(inverse)
+}
+
+fn apply_operation(table: &mut TableDefinition, operation: &AlterOperation) -> TikalResult<()> {
+    match operation {
+        AlterOperation::AddColumn(column) => {
+            if table.columns.iter().any(|c| c.name == column.name) {
+                return Err(TikalError::validation(
+                    &column.name,
+                    &format!("Column already exists on table '{}'", table.name),
+                ));
+            }
+            table.columns.push(column.clone());
+        }
+        AlterOperation::DropColumn(name) => {
+            column_mut(table, name)?;
+            table.columns.retain(|c| c.name != *name);
+            table.indexes.retain(|i| !i.columns.contains(name));
+            table.foreign_keys.retain(|fk| !fk.columns.contains(name));
+            table
+                .unrepresented
+                .retain(|state| state.column() != Some(name.as_str()));
+        }
+        AlterOperation::RenameColumn { from, to } => {
+            column_mut(table, from)?.name = to.clone();
+            let columns = table
+                .indexes
+                .iter_mut()
+                .flat_map(|i| i.columns.iter_mut())
+                .chain(
+                    table
+                        .foreign_keys
+                        .iter_mut()
+                        .flat_map(|fk| fk.columns.iter_mut()),
+                );
+            for column in columns.filter(|c| *c == from) {
+                *column = to.clone();
+            }
+            for state in table.unrepresented.iter_mut() {
+                match state {
+                    UnrepresentedState::Type { column, .. }
+                    | UnrepresentedState::Default { column, .. }
+                    | UnrepresentedState::Column { column, .. }
+                        if column == from =>
+                    {
+                        *column = to.clone()
+                    }
+                    _ => {}
+                }
+            }
+        }
+        // A new type or default replaces the one that could not be expressed
+        AlterOperation::ChangeType {
+            column,
+            column_type,
+        } => {
+            column_mut(table, column)?.column_type = column_type.clone();
+            table.unrepresented.retain(
+                |state| !matches!(state, UnrepresentedState::Type { column: c, .. } if c == column),
+            );
+        }
+        AlterOperation::SetNullable { column, nullable } => {
+            column_mut(table, column)?.nullable = *nullable
+        }
+        AlterOperation::SetDefault {
+            column,
+            default_value,
+        } => {
+            column_mut(table, column)?.default_value = default_value.clone();
+            table.unrepresented.retain(
+                |state| !matches!(state, UnrepresentedState::Default { column: c, .. } if c == column),
+            );
+        }
+        AlterOperation::AddIndex(index) => table.indexes.push(index.clone()),
+        AlterOperation::DropIndex(name) => {
+            if !table.indexes.iter().any(|i| i.name == *name) {
+                return Err(TikalError::validation(
+                    name,
+                    &format!("Index does not exist on table '{}'", table.name),
+                ));
+            }
+            table.indexes.retain(|i| i.name != *name);
+        }
+        AlterOperation::AddForeignKey(foreign_key) => table.foreign_keys.push(foreign_key.clone()),
+        AlterOperation::DropForeignKey(name) => {
+            if !table.foreign_keys.iter().any(|fk| fk.name == *name) {
+                return Err(TikalError::validation(
+                    name,
+                    &format!("Foreign key does not exist on table '{}'", table.name),
+                ));
+            }
+            table.foreign_keys.retain(|fk| fk.name != *name);
+        }
+        AlterOperation::RenameTable(name) => table.name = name.clone(),
+    }
+    Ok(())
+}
+
+fn column_mut<'a>(
+    table: &'a mut TableDefinition,
+    name: &str,
+) -> TikalResult<&'a mut ColumnDefinition> {
+    let table_name = table.name.clone();
+    table
+        .columns
+        .iter_mut()
+        .find(|c| c.name == name)
+        .ok_or_else(|| {
+            TikalError::validation(
+                name,
+                &format!("Column does not exist on table '{}'", table_name),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::schema::builders::{AlterTableBuilder, TableBuilder};
+    use crate::infrastructure::schema::types::ColumnType;
+
+    fn users() -> TableDefinition {
+        TableBuilder::new("users")
+            .id()
+            .column("name", ColumnType::Text)
+            .finish()
+            .column("email", ColumnType::Text)
+            .finish()
+            .column("age", ColumnType::Int)
+            .finish()
+            .index(vec!["name".to_string()])
+            .name("users_name_index")
+            .finish()
+            .build()
+    }
+
+    fn copy_statement(statements: &[String]) -> &str {
+        statements
+            .iter()
+            .find(|sql| sql.starts_with("INSERT INTO"))
+            .expect("rebuild copies the rows")
+    }
+
+    #[test]
+    fn sqlite_rebuild_copies_renamed_columns_and_skips_dropped_ones() {
+        let alter = AlterTableBuilder::new("users")
+            .rename_column("name", "full_name")
+            .drop_column("age")
+            .change_type("email", ColumnType::LongText)
+            .build();
+
+        let statements = UnifiedDdlGenerator::sqlite()
+            .generate_alter_table(&alter, &users())
+            .unwrap();
+
+        assert_eq!(
+            copy_statement(&statements),
+            "INSERT INTO \"users\" (\"id\", \"full_name\", \"email\") \
+             SELECT \"id\", \"name\", \"email\" FROM \"__tikal_old_users\""
+        );
+        assert_eq!(
+            statements.last().unwrap(),
+            "CREATE INDEX \"users_name_index\" ON \"users\" (\"full_name\")"
+        );
+    }
+
+    #[test]
+    fn sqlite_rebuild_leaves_replaced_columns_empty() {
+        // A column dropped and added again has no data to carry over, even
+        // under a name the old table had
+        let alter = AlterTableBuilder::new("users")
+            .rename_column("age", "years")
+            .drop_column("years")
+            .drop_column("email")
+            .add_column("email", ColumnType::Text)
+            .unique()
+            .finish()
+            .build();
+
+        let statements = UnifiedDdlGenerator::sqlite()
+            .generate_alter_table(&alter, &users())
+            .unwrap();
+
+        assert_eq!(
+            copy_statement(&statements),
+            "INSERT INTO \"users\" (\"id\", \"name\") \
+             SELECT \"id\", \"name\" FROM \"__tikal_old_users\""
+        );
+    }
+
+    #[test]
+    fn sqlite_renames_without_rebuilding() {
+        let alter = AlterTableBuilder::new("users")
+            .rename_column("name", "full_name")
+            .build();
+
+        let statements = UnifiedDdlGenerator::sqlite()
+            .generate_alter_table(&alter, &users())
+            .unwrap();
+
+        assert_eq!(
+            statements,
+            vec!["ALTER TABLE \"users\" RENAME COLUMN \"name\" TO \"full_name\""]
+        );
+    }
+
+    #[test]
+    fn rebuild_refuses_to_lose_unrepresented_state() {
+        let mut current = users();
+        current
+            .unrepresented
+            .push(UnrepresentedState::Table("CHECK (age > 0)".to_string()));
+        let alter = AlterTableBuilder::new("users")
+            .change_type("email", ColumnType::LongText)
+            .build();
+
+        let result = UnifiedDdlGenerator::sqlite().generate_alter_table(&alter, &current);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn dropping_a_column_drops_state_tied_to_it() {
+        let mut current = users();
+        current.unrepresented.push(UnrepresentedState::Type {
+            column: "age".to_string(),
+            sql_type: "SMALLINT".to_string(),
+        });
+        let alter = AlterTableBuilder::new("users")
+            .drop_column("age")
+            .change_type("email", ColumnType::LongText)
+            .build();
+
+        let result = UnifiedDdlGenerator::sqlite().generate_alter_table(&alter, &current);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reversing_restores_renamed_and_dropped_columns() {
+        let current = users();
+        let alter = AlterTableBuilder::new("users")
+            .rename_column("name", "full_name")
+            .drop_column("age")
+            .build();
+
+        let (altered, reverse) = reverse_alteration(&current, &alter);
+        let mut restored = altered.clone();
+        for operation in &reverse.operations {
+            apply_operation(&mut restored, operation).unwrap();
+        }
+
+        assert!(altered.columns.iter().all(|c| c.name != "age"));
+        let names = |table: &TableDefinition| -> Vec<String> {
+            table.columns.iter().map(|c| c.name.clone()).collect()
+        };
+        assert_eq!(names(&restored), vec!["id", "name", "email", "age"]);
+    }
+}
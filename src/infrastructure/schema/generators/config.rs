@@ -1,4 +1,5 @@
 use super::super::types::ColumnType;
+use crate::domain::value_objects::Value;
 use std::collections::HashMap;
 
 #[derive(Clone)]
@@ -9,6 +10,9 @@ pub struct DdlConfig {
     pub auto_increment_syntax: AutoIncrementStyle,
     pub table_options: &'static str,
     pub supports_cascade: bool,
+    pub alter_style: AlterStyle,
+    pub enum_style: EnumStyle,
+    pub supports_arrays: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -21,6 +25,26 @@ pub enum AutoIncrementStyle {
     SuffixWithKeyword(&'static str),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EnumStyle {
+    // CREATE TYPE ... AS ENUM, referenced by name
+    NamedType,
+    // ENUM('a', 'b') as the column type
+    Inline,
+    // A text column with a CHECK (... IN (...)) constraint
+    Check,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AlterStyle {
+    // ALTER COLUMN ... TYPE / SET NOT NULL / SET DEFAULT
+    Standard,
+    // MODIFY COLUMN with the full column definition
+    Modify,
+    // Recreate the table for anything beyond rename, add column and indexes
+    Rebuild,
+}
+
 impl DdlConfig {
     pub fn mysql() -> Self {
         let mut type_mappings = Self::default_type_mappings();
@@ -38,6 +62,9 @@ impl DdlConfig {
             auto_increment_syntax: AutoIncrementStyle::Suffix("AUTO_INCREMENT"),
             table_options: " ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci",
             supports_cascade: true,
+            alter_style: AlterStyle::Modify,
+            enum_style: EnumStyle::Inline,
+            supports_arrays: false,
         }
     }
 
@@ -49,6 +76,9 @@ impl DdlConfig {
         type_mappings.insert(ColumnType::NaiveDateTime, "TIMESTAMP");
         type_mappings.insert(ColumnType::Json, "JSONB");
         type_mappings.insert(ColumnType::Binary, "BYTEA");
+        type_mappings.insert(ColumnType::Decimal, "NUMERIC");
+        type_mappings.insert(ColumnType::Uuid, "UUID");
+        type_mappings.insert(ColumnType::Interval, "INTERVAL");
 
         Self {
             name: "PostgreSQL",
@@ -60,6 +90,9 @@ impl DdlConfig {
             },
             table_options: ";",
             supports_cascade: true,
+            alter_style: AlterStyle::Standard,
+            enum_style: EnumStyle::NamedType,
+            supports_arrays: true,
         }
     }
 
@@ -72,6 +105,11 @@ impl DdlConfig {
         type_mappings.insert(ColumnType::DateTime, "TEXT");
         type_mappings.insert(ColumnType::NaiveDateTime, "TEXT");
         type_mappings.insert(ColumnType::Json, "TEXT");
+        type_mappings.insert(ColumnType::Decimal, "TEXT");
+        type_mappings.insert(ColumnType::Uuid, "TEXT");
+        type_mappings.insert(ColumnType::Date, "TEXT");
+        type_mappings.insert(ColumnType::Time, "TEXT");
+        type_mappings.insert(ColumnType::Interval, "TEXT");
 
         Self {
             name: "SQLite",
@@ -80,6 +118,9 @@ impl DdlConfig {
             auto_increment_syntax: AutoIncrementStyle::SuffixWithKeyword("AUTOINCREMENT"),
             table_options: ";",
             supports_cascade: false,
+            alter_style: AlterStyle::Rebuild,
+            enum_style: EnumStyle::Check,
+            supports_arrays: false,
         }
     }
 
@@ -96,6 +137,12 @@ impl DdlConfig {
         map.insert(ColumnType::NaiveDateTime, "DATETIME");
         map.insert(ColumnType::Json, "JSON");
         map.insert(ColumnType::Binary, "BLOB");
+        map.insert(ColumnType::Decimal, "DECIMAL(38, 10)");
+        map.insert(ColumnType::Uuid, "CHAR(36)");
+        map.insert(ColumnType::Date, "DATE");
+        map.insert(ColumnType::Time, "TIME");
+        // ISO 8601 text where there is no native interval type
+        map.insert(ColumnType::Interval, "VARCHAR(64)");
         map
     }
 
@@ -107,6 +154,30 @@ impl DdlConfig {
         self.type_mappings.get(col_type).copied().unwrap_or("TEXT")
     }
 
+    // Arrays are stored as JSON where there is no native array type
+    pub fn sql_type(&self, col_type: &ColumnType) -> String {
+        match col_type {
+            ColumnType::Array(inner) if self.supports_arrays => {
+                format!("{}[]", self.sql_type(inner))
+            }
+            ColumnType::Array(_) => self.map_type(&ColumnType::Json).to_string(),
+            ColumnType::Enum { name, variants } => match self.enum_style {
+                EnumStyle::NamedType => self.quote_identifier(name),
+                EnumStyle::Inline => format!("ENUM({})", self.enum_variants_sql(variants)),
+                EnumStyle::Check => self.map_type(&ColumnType::Text).to_string(),
+            },
+            _ => self.map_type(col_type).to_string(),
+        }
+    }
+
+    pub fn enum_variants_sql(&self, variants: &[String]) -> String {
+        variants
+            .iter()
+            .map(|v| Value::Text(v.clone()).to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     pub fn auto_increment_sql(&self, col: &super::super::types::ColumnDefinition) -> String {
         if !col.auto_increment {
             return String::new();
@@ -135,9 +206,9 @@ impl DdlConfig {
             } if col.auto_increment => match col.column_type {
                 ColumnType::Int => int_type.to_string(),
                 ColumnType::Id | ColumnType::BigInt => bigint_type.to_string(),
-                _ => self.map_type(&col.column_type).to_string(),
+                _ => self.sql_type(&col.column_type),
             },
-            _ => self.map_type(&col.column_type).to_string(),
+            _ => self.sql_type(&col.column_type),
         }
     }
 }
@@ -1,8 +1,10 @@
+mod alter;
 pub mod config;
 pub mod r#trait;
 pub mod unified;
 
-pub use config::DdlConfig;
+pub(crate) use alter::reverse_alteration;
+pub use config::{AlterStyle, DdlConfig, EnumStyle};
 pub use r#trait::DdlGenerator;
 pub use unified::UnifiedDdlGenerator;
 
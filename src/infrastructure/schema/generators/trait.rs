@@ -1,10 +1,16 @@
-use super::super::types::{ColumnDefinition, TableDefinition};
+use super::super::types::{AlterTableDefinition, ColumnDefinition, ColumnType, TableDefinition};
+use crate::domain::TikalResult;
 
 pub trait DdlGenerator {
     fn generate_create_table(&self, table: &TableDefinition) -> String;
 
     fn generate_drop_table(&self, table_name: &str) -> String;
 
+    // Types the columns need before they can be created, such as named enums
+    fn generate_create_types(&self, _columns: &[ColumnDefinition]) -> Vec<String> {
+        Vec::new()
+    }
+
     fn generate_create_index(
         &self,
         table_name: &str,
@@ -31,7 +37,15 @@ pub trait DdlGenerator {
         )
     }
 
+    fn generate_alter_table(
+        &self,
+        alter: &AlterTableDefinition,
+        current: &TableDefinition,
+    ) -> TikalResult<Vec<String>>;
+
     fn generate_column_definition(&self, column: &ColumnDefinition) -> String;
 
+    fn generate_column_type(&self, column_type: &ColumnType) -> String;
+
     fn quote_identifier(&self, identifier: &str) -> String;
 }
@@ -1,6 +1,12 @@
-use super::super::types::{ColumnDefinition, TableDefinition};
-use super::config::DdlConfig;
+use super::super::types::{
+    AlterTableDefinition, ColumnDefinition, ColumnType, ForeignKeyDefinition, ReferentialAction,
+    TableDefinition,
+};
+use super::config::{DdlConfig, EnumStyle};
 use super::r#trait::DdlGenerator;
+use crate::domain::repositories::types::DriverType;
+use crate::domain::value_objects::Value;
+use crate::domain::TikalResult;
 
 #[derive(Clone)]
 pub struct UnifiedDdlGenerator {
@@ -33,9 +39,42 @@ impl UnifiedDdlGenerator {
         }
     }
 
+    pub fn for_driver(driver_type: &DriverType) -> Self {
+        match driver_type {
+            DriverType::MySQL => Self::mysql(),
+            DriverType::PostgreSQL => Self::postgres(),
+            DriverType::SQLite => Self::sqlite(),
+        }
+    }
+
     pub fn config(&self) -> &DdlConfig {
         &self.config
     }
+
+    pub fn generate_foreign_key(&self, foreign_key: &ForeignKeyDefinition) -> String {
+        let quote_list = |columns: &[String]| {
+            columns
+                .iter()
+                .map(|c| self.quote_identifier(c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut sql = format!(
+            "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+            self.quote_identifier(&foreign_key.name),
+            quote_list(&foreign_key.columns),
+            self.quote_identifier(&foreign_key.referenced_table),
+            quote_list(&foreign_key.referenced_columns)
+        );
+        if foreign_key.on_delete != ReferentialAction::NoAction {
+            sql.push_str(&format!(" ON DELETE {}", foreign_key.on_delete.as_sql()));
+        }
+        if foreign_key.on_update != ReferentialAction::NoAction {
+            sql.push_str(&format!(" ON UPDATE {}", foreign_key.on_update.as_sql()));
+        }
+        sql
+    }
 }
 
 impl DdlGenerator for UnifiedDdlGenerator {
@@ -48,6 +87,12 @@ impl DdlGenerator for UnifiedDdlGenerator {
             .columns
             .iter()
             .map(|col| format!("  {}", self.generate_column_definition(col)))
+            .chain(
+                table
+                    .foreign_keys
+                    .iter()
+                    .map(|fk| format!("  {}", self.generate_foreign_key(fk))),
+            )
             .collect();
 
         sql.push_str(&column_defs.join(",\n"));
@@ -67,6 +112,60 @@ impl DdlGenerator for UnifiedDdlGenerator {
         sql
     }
 
+    // The assignment cast lets text parameters be inserted into enum columns.
+    // Types are created once and left in place when their tables are dropped.
+    fn generate_create_types(&self, columns: &[ColumnDefinition]) -> Vec<String> {
+        if self.config.enum_style != EnumStyle::NamedType {
+            return Vec::new();
+        }
+
+        let mut statements = Vec::new();
+        let mut created: Vec<&str> = Vec::new();
+        for column in columns {
+            let mut column_type = &column.column_type;
+            while let ColumnType::Array(inner) = column_type {
+                column_type = inner;
+            }
+            let ColumnType::Enum { name, variants } = column_type else {
+                continue;
+            };
+            if created.contains(&name.as_str()) {
+                continue;
+            }
+            created.push(name);
+
+            // An existing type gains the new labels; labels it would lose are
+            // an error, as PostgreSQL cannot drop a value from an enum
+            let quoted = self.quote_identifier(name);
+            let variants_sql = self.config.enum_variants_sql(variants);
+            let add_values: String = variants
+                .iter()
+                .map(|v| {
+                    format!(
+                        "ALTER TYPE {} ADD VALUE IF NOT EXISTS {}; ",
+                        quoted,
+                        Value::Text(v.clone())
+                    )
+                })
+                .collect();
+            statements.push(format!(
+                "DO $$ DECLARE removed text; BEGIN \
+                 CREATE TYPE {quoted} AS ENUM ({variants_sql}); \
+                 CREATE CAST (text AS {quoted}) WITH INOUT AS ASSIGNMENT; \
+                 EXCEPTION WHEN duplicate_object THEN \
+                 SELECT string_agg(enumlabel::text, ', ') INTO removed FROM pg_enum \
+                 WHERE enumtypid = {type_name}::regtype \
+                 AND enumlabel::text <> ALL (ARRAY[{variants_sql}]::text[]); \
+                 IF removed IS NOT NULL THEN RAISE EXCEPTION \
+                 'Enum type % has values % that cannot be dropped, change it with raw SQL', \
+                 {type_name}, removed; END IF; \
+                 {add_values}END $$",
+                type_name = Value::Text(quoted.clone()),
+            ));
+        }
+        statements
+    }
+
     fn generate_create_index(
         &self,
         table_name: &str,
@@ -94,6 +193,14 @@ impl DdlGenerator for UnifiedDdlGenerator {
         format!("DROP INDEX IF EXISTS {}", self.quote_identifier(index_name))
     }
 
+    fn generate_alter_table(
+        &self,
+        alter: &AlterTableDefinition,
+        current: &TableDefinition,
+    ) -> TikalResult<Vec<String>> {
+        self.alter_table_statements(alter, current)
+    }
+
     fn generate_column_definition(&self, col: &ColumnDefinition) -> String {
         let mut parts = vec![self.quote_identifier(&col.name)];
 
@@ -120,9 +227,23 @@ impl DdlGenerator for UnifiedDdlGenerator {
             parts.push(format!("DEFAULT {}", default));
         }
 
+        if let ColumnType::Enum { variants, .. } = &col.column_type
+            && self.config.enum_style == EnumStyle::Check
+        {
+            parts.push(format!(
+                "CHECK ({} IN ({}))",
+                self.quote_identifier(&col.name),
+                self.config.enum_variants_sql(variants)
+            ));
+        }
+
         parts.join(" ")
     }
 
+    fn generate_column_type(&self, column_type: &ColumnType) -> String {
+        self.config.sql_type(column_type)
+    }
+
     fn quote_identifier(&self, identifier: &str) -> String {
         self.config.quote_identifier(identifier)
     }
@@ -137,3 +258,125 @@ impl Default for MySqlDdlGenerator {
         Self::mysql()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::schema::builders::{AlterTableBuilder, TableBuilder};
+
+    fn mood() -> ColumnType {
+        ColumnType::Enum {
+            name: "mood".to_string(),
+            variants: vec!["happy".to_string(), "it's fine".to_string()],
+        }
+    }
+
+    fn column(column_type: ColumnType) -> ColumnDefinition {
+        TableBuilder::new("things")
+            .column("mood", column_type)
+            .finish()
+            .build()
+            .columns
+            .remove(0)
+    }
+
+    #[test]
+    fn enum_columns_follow_the_driver_enum_style() {
+        assert_eq!(
+            UnifiedDdlGenerator::postgres().generate_column_definition(&column(mood())),
+            "\"mood\" \"mood\" NOT NULL"
+        );
+        assert_eq!(
+            UnifiedDdlGenerator::mysql().generate_column_definition(&column(mood())),
+            "`mood` ENUM('happy', 'it''s fine') NOT NULL"
+        );
+        assert_eq!(
+            UnifiedDdlGenerator::sqlite().generate_column_definition(&column(mood())),
+            "\"mood\" TEXT NOT NULL CHECK (\"mood\" IN ('happy', 'it''s fine'))"
+        );
+    }
+
+    #[test]
+    fn arrays_are_native_on_postgres_and_json_elsewhere() {
+        let nested = ColumnType::Array(Box::new(ColumnType::Array(Box::new(mood()))));
+        let ints = ColumnType::Array(Box::new(ColumnType::Int));
+
+        let postgres = UnifiedDdlGenerator::postgres();
+        assert_eq!(postgres.generate_column_type(&ints), "INTEGER[]");
+        assert_eq!(postgres.generate_column_type(&nested), "\"mood\"[][]");
+        assert_eq!(
+            UnifiedDdlGenerator::mysql().generate_column_type(&nested),
+            "JSON"
+        );
+        assert_eq!(
+            UnifiedDdlGenerator::sqlite().generate_column_type(&ints),
+            "TEXT"
+        );
+    }
+
+    #[test]
+    fn postgres_creates_each_enum_type_once() {
+        let columns = [
+            column(mood()),
+            column(ColumnType::Array(Box::new(mood()))),
+            column(ColumnType::Text),
+        ];
+
+        let statements = UnifiedDdlGenerator::postgres().generate_create_types(&columns);
+
+        assert_eq!(statements.len(), 1);
+        let sql = &statements[0];
+        assert!(sql.starts_with("DO $$ DECLARE removed text; BEGIN "));
+        assert!(sql.contains("CREATE TYPE \"mood\" AS ENUM ('happy', 'it''s fine');"));
+        assert!(sql.contains("CREATE CAST (text AS \"mood\") WITH INOUT AS ASSIGNMENT;"));
+        assert!(sql.contains("WHERE enumtypid = '\"mood\"'::regtype"));
+        assert!(sql.contains("ALTER TYPE \"mood\" ADD VALUE IF NOT EXISTS 'it''s fine';"));
+        assert!(sql.ends_with("END $$"));
+    }
+
+    #[test]
+    fn only_postgres_creates_enum_types() {
+        let columns = [column(mood())];
+        assert!(
+            UnifiedDdlGenerator::mysql()
+                .generate_create_types(&columns)
+                .is_empty()
+        );
+        assert!(
+            UnifiedDdlGenerator::sqlite()
+                .generate_create_types(&columns)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn postgres_creates_enum_types_before_altering_columns() {
+        let current = TableBuilder::new("things")
+            .id()
+            .column("status", ColumnType::Text)
+            .finish()
+            .build();
+        let alter = AlterTableBuilder::new("things")
+            .add_column("mood", mood())
+            .nullable()
+            .finish()
+            .change_type("status", mood())
+            .build();
+
+        let statements = UnifiedDdlGenerator::postgres()
+            .generate_alter_table(&alter, &current)
+            .unwrap();
+
+        assert_eq!(statements.len(), 4);
+        assert!(statements[0].contains("CREATE TYPE \"mood\""));
+        assert_eq!(
+            statements[1],
+            "ALTER TABLE \"things\" ADD COLUMN \"mood\" \"mood\""
+        );
+        assert!(statements[2].contains("CREATE TYPE \"mood\""));
+        assert_eq!(
+            statements[3],
+            "ALTER TABLE \"things\" ALTER COLUMN \"status\" TYPE \"mood\" USING \"status\"::\"mood\""
+        );
+    }
+}
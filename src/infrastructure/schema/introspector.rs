@@ -0,0 +1,828 @@
+use super::generators::{DdlGenerator, UnifiedDdlGenerator};
+use super::types::{
+    ColumnDefinition, ColumnType, ForeignKeyDefinition, IndexDefinition, ReferentialAction,
+    SchemaDefinition, TableDefinition, UnrepresentedState,
+};
+use crate::domain::repositories::executor::QueryExecutor;
+use crate::domain::repositories::types::DriverType;
+use crate::domain::value_objects::Value;
+use crate::domain::TikalResult;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+type Row = HashMap<String, Value>;
+
+struct IntrospectedIndex {
+    index: IndexDefinition,
+    // Backs a UNIQUE constraint rather than a CREATE INDEX statement
+    constraint: bool,
+}
+
+pub struct SchemaIntrospector<'a> {
+    executor: &'a dyn QueryExecutor,
+    driver_type: DriverType,
+}
+
+impl<'a> SchemaIntrospector<'a> {
+    pub fn new(executor: &'a dyn QueryExecutor) -> Self {
+        Self {
+            executor,
+            driver_type: executor.driver_info().driver_type,
+        }
+    }
+
+    pub async fn introspect(&self) -> TikalResult<SchemaDefinition> {
+        let mut tables = Vec::new();
+        for name in self.table_names().await? {
+            tables.push(self.introspect_table(&name).await?);
+        }
+        Ok(SchemaDefinition { tables })
+    }
+
+    pub async fn introspect_table(&self, table_name: &str) -> TikalResult<TableDefinition> {
+        let (mut columns, unrepresented) = self.columns(table_name).await?;
+
+        let mut indexes = Vec::new();
+        for introspected in self.indexes(table_name).await? {
+            let index = introspected.index;
+            if introspected.constraint && index.unique && index.columns.len() == 1 {
+                if let Some(column) = columns.iter_mut().find(|c| c.name == index.columns[0]) {
+                    column.unique = true;
+                }
+                continue;
+            }
+            indexes.push(index);
+        }
+
+        Ok(TableDefinition {
+            name: table_name.to_string(),
+            columns,
+            indexes,
+            foreign_keys: self.foreign_keys(table_name).await?,
+            unrepresented,
+        })
+    }
+
+    pub async fn table_names(&self) -> TikalResult<Vec<String>> {
+        let sql = match self.driver_type {
+            DriverType::PostgreSQL => {
+                "SELECT table_name::text AS table_name FROM information_schema.tables \
+                 WHERE table_schema = current_schema() AND table_type = 'BASE TABLE' \
+                 ORDER BY table_name"
+            }
+            DriverType::MySQL => {
+                "SELECT table_name AS table_name FROM information_schema.tables \
+                 WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE' \
+                 ORDER BY table_name"
+            }
+            DriverType::SQLite => {
+                "SELECT name AS table_name FROM sqlite_master \
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+            }
+        };
+
+        let rows = self.executor.fetch_all(sql, vec![]).await?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| text(row, "table_name"))
+            .collect())
+    }
+
+    // PostgreSQL alters columns in place, so nothing it reports is ever restated
+    async fn columns(
+        &self,
+        table_name: &str,
+    ) -> TikalResult<(Vec<ColumnDefinition>, Vec<UnrepresentedState>)> {
+        match self.driver_type {
+            DriverType::PostgreSQL => Ok((self.postgres_columns(table_name).await?, Vec::new())),
+            DriverType::MySQL => self.mysql_columns(table_name).await,
+            DriverType::SQLite => self.sqlite_columns(table_name).await,
+        }
+    }
+
+    async fn indexes(&self, table_name: &str) -> TikalResult<Vec<IntrospectedIndex>> {
+        match self.driver_type {
+            DriverType::PostgreSQL => self.postgres_indexes(table_name).await,
+            DriverType::MySQL => self.mysql_indexes(table_name).await,
+            DriverType::SQLite => self.sqlite_indexes(table_name).await,
+        }
+    }
+
+    async fn foreign_keys(&self, table_name: &str) -> TikalResult<Vec<ForeignKeyDefinition>> {
+        match self.driver_type {
+            DriverType::PostgreSQL => self.postgres_foreign_keys(table_name).await,
+            DriverType::MySQL => self.mysql_foreign_keys(table_name).await,
+            DriverType::SQLite => self.sqlite_foreign_keys(table_name).await,
+        }
+    }
+
+    async fn postgres_columns(&self, table_name: &str) -> TikalResult<Vec<ColumnDefinition>> {
+        let primary_keys: Vec<String> = self
+            .executor
+            .fetch_all(
+                "SELECT kcu.column_name::text AS column_name \
+                 FROM information_schema.table_constraints tc \
+                 JOIN information_schema.key_column_usage kcu \
+                   ON tc.constraint_name = kcu.constraint_name \
+                  AND tc.table_schema = kcu.table_schema \
+                 WHERE tc.constraint_type = 'PRIMARY KEY' \
+                   AND tc.table_schema = current_schema() AND tc.table_name = $1",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?
+            .iter()
+            .filter_map(|row| text(row, "column_name"))
+            .collect();
+
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT column_name::text AS column_name, data_type::text AS data_type, \
+                        udt_name::text AS udt_name, is_nullable::text AS is_nullable, column_default::text AS column_default, \
+                        is_identity::text AS is_identity \
+                 FROM information_schema.columns \
+                 WHERE table_schema = current_schema() AND table_name = $1 \
+                 ORDER BY ordinal_position",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        let enums = if rows.iter().any(|row| {
+            matches!(
+                text(row, "data_type").as_deref(),
+                Some("USER-DEFINED" | "ARRAY")
+            )
+        }) {
+            self.postgres_enums().await?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let name = text(row, "column_name")?;
+                let udt_name = text(row, "udt_name").unwrap_or_default();
+                let column_type = match text(row, "data_type")?.as_str() {
+                    // udt_name is the element type prefixed with an underscore
+                    "ARRAY" => {
+                        let element = udt_name.trim_start_matches('_');
+                        ColumnType::Array(Box::new(postgres_type(element, &enums)))
+                    }
+                    "USER-DEFINED" => postgres_type(&udt_name, &enums),
+                    data_type => column_type_from_sql(data_type),
+                };
+                let default = text(row, "column_default").unwrap_or_default();
+                Some(ColumnDefinition {
+                    nullable: text(row, "is_nullable").as_deref() == Some("YES"),
+                    primary_key: primary_keys.contains(&name),
+                    auto_increment: default.starts_with("nextval(")
+                        || text(row, "is_identity").as_deref() == Some("YES"),
+                    default_value: parse_default(&default, &column_type),
+                    unique: false,
+                    column_type,
+                    name,
+                })
+            })
+            .collect())
+    }
+
+    async fn postgres_enums(&self) -> TikalResult<HashMap<String, Vec<String>>> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT t.typname::text AS name, e.enumlabel::text AS label \
+                 FROM pg_type t \
+                 JOIN pg_enum e ON e.enumtypid = t.oid \
+                 JOIN pg_namespace n ON n.oid = t.typnamespace \
+                 WHERE n.nspname = current_schema() \
+                 ORDER BY t.typname, e.enumsortorder",
+                vec![],
+            )
+            .await?;
+
+        let mut enums: HashMap<String, Vec<String>> = HashMap::new();
+        for row in &rows {
+            if let (Some(name), Some(label)) = (text(row, "name"), text(row, "label")) {
+                enums.entry(name).or_default().push(label);
+            }
+        }
+        Ok(enums)
+    }
+
+    async fn mysql_columns(
+        &self,
+        table_name: &str,
+    ) -> TikalResult<(Vec<ColumnDefinition>, Vec<UnrepresentedState>)> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT c.column_name AS column_name, c.column_type AS column_type, \
+                        c.is_nullable AS is_nullable, c.column_key AS column_key, c.extra AS extra, \
+                        c.column_default AS column_default, c.collation_name AS collation_name, \
+                        c.column_comment AS column_comment, t.table_collation AS table_collation \
+                 FROM information_schema.columns c \
+                 JOIN information_schema.tables t \
+                   ON t.table_schema = c.table_schema AND t.table_name = c.table_name \
+                 WHERE c.table_schema = DATABASE() AND c.table_name = ? \
+                 ORDER BY c.ordinal_position",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        let ddl = UnifiedDdlGenerator::mysql();
+        let mut columns = Vec::new();
+        let mut unrepresented = Vec::new();
+        for row in &rows {
+            let (Some(name), Some(sql_type)) = (text(row, "column_name"), text(row, "column_type"))
+            else {
+                continue;
+            };
+            // MySQL enums have no name of their own, so they take the column's
+            let column_type = match mysql_enum_variants(&sql_type) {
+                Some(variants) => ColumnType::Enum {
+                    name: name.clone(),
+                    variants,
+                },
+                None => column_type_from_sql(&sql_type),
+            };
+            if mysql_type(&sql_type) != mysql_type(&ddl.generate_column_type(&column_type)) {
+                unrepresented.push(UnrepresentedState::Type {
+                    column: name.clone(),
+                    sql_type: sql_type.clone(),
+                });
+            }
+
+            let extra = text(row, "extra").unwrap_or_default();
+            // MySQL reports literal defaults unquoted and flags expressions in EXTRA
+            let default = text(row, "column_default");
+            let default_value = match &default {
+                Some(_) if extra.contains("DEFAULT_GENERATED") => None,
+                Some(default) if default.starts_with('\'') => parse_default(default, &column_type),
+                Some(default) => Some(literal_value(default.clone(), &column_type)),
+                None => None,
+            };
+            if let Some(expression) = default.filter(|_| default_value.is_none()) {
+                unrepresented.push(UnrepresentedState::Default {
+                    column: name.clone(),
+                    expression,
+                });
+            }
+
+            let mut descriptions = Vec::new();
+            let extra_lower = extra.to_lowercase();
+            if extra_lower.contains("on update") {
+                descriptions.push(extra.clone());
+            }
+            if extra_lower.contains("virtual generated") || extra_lower.contains("stored generated")
+            {
+                descriptions.push("generation expression".to_string());
+            }
+            if let Some(collation) = text(row, "collation_name")
+                && text(row, "table_collation") != Some(collation.clone())
+            {
+                descriptions.push(format!("collation {}", collation));
+            }
+            if text(row, "column_comment").is_some_and(|comment| !comment.is_empty()) {
+                descriptions.push("comment".to_string());
+            }
+            unrepresented.extend(descriptions.into_iter().map(|description| {
+                UnrepresentedState::Column {
+                    column: name.clone(),
+                    description,
+                }
+            }));
+
+            columns.push(ColumnDefinition {
+                nullable: text(row, "is_nullable").as_deref() == Some("YES"),
+                primary_key: text(row, "column_key").as_deref() == Some("PRI"),
+                auto_increment: extra.contains("auto_increment"),
+                default_value,
+                unique: false,
+                column_type,
+                name,
+            });
+        }
+        Ok((columns, unrepresented))
+    }
+
+    async fn sqlite_columns(
+        &self,
+        table_name: &str,
+    ) -> TikalResult<(Vec<ColumnDefinition>, Vec<UnrepresentedState>)> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT name, type, \"notnull\" AS not_null, dflt_value, pk \
+                 FROM pragma_table_info(?) ORDER BY cid",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+        let table_sql = self
+            .executor
+            .fetch_all(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?
+            .first()
+            .and_then(|row| text(row, "sql"))
+            .unwrap_or_default();
+
+        let ddl = UnifiedDdlGenerator::sqlite();
+        // Enum checks written by Tikal are cut out, leaving constraints it did not write
+        let mut remaining_sql = table_sql.clone();
+        let mut columns = Vec::new();
+        let mut unrepresented = Vec::new();
+        for row in &rows {
+            let Some(name) = text(row, "name") else {
+                continue;
+            };
+            let sql_type = text(row, "type").unwrap_or_default();
+            // SQLite integers are always 64-bit
+            let column_type = match column_type_from_sql(&sql_type) {
+                ColumnType::Int if sql_type.eq_ignore_ascii_case("INTEGER") => ColumnType::BigInt,
+                column_type => column_type,
+            };
+            let check = format!("CHECK ({} IN (", ddl.quote_identifier(&name));
+            let column_type = match sqlite_enum_check(&table_sql, &check) {
+                Some((variants, span)) if column_type == ColumnType::Text => {
+                    remaining_sql = remaining_sql.replacen(&table_sql[span], "", 1);
+                    ColumnType::Enum {
+                        name: name.clone(),
+                        variants,
+                    }
+                }
+                _ => column_type,
+            };
+            // The declared type decides affinity and whether an INTEGER key aliases the rowid
+            if !sql_type.eq_ignore_ascii_case(&ddl.generate_column_type(&column_type)) {
+                unrepresented.push(UnrepresentedState::Type {
+                    column: name.clone(),
+                    sql_type: sql_type.clone(),
+                });
+            }
+
+            let default = text(row, "dflt_value");
+            let default_value = default
+                .as_deref()
+                .and_then(|default| parse_default(default, &column_type));
+            if let Some(expression) = default.filter(|_| default_value.is_none()) {
+                unrepresented.push(UnrepresentedState::Default {
+                    column: name.clone(),
+                    expression,
+                });
+            }
+
+            let primary_key = int(row, "pk") > 0;
+            columns.push(ColumnDefinition {
+                nullable: int(row, "not_null") == 0 && !primary_key,
+                primary_key,
+                // An INTEGER PRIMARY KEY column aliases the rowid and is assigned automatically
+                auto_increment: primary_key && sql_type.eq_ignore_ascii_case("INTEGER"),
+                default_value,
+                unique: false,
+                column_type,
+                name,
+            });
+        }
+
+        unrepresented.extend(
+            SQLITE_TABLE_STATE
+                .iter()
+                .filter(|(pattern, _)| pattern.is_match(&remaining_sql))
+                .map(|(_, description)| UnrepresentedState::Table(description.to_string())),
+        );
+        let triggers = self
+            .executor
+            .fetch_all(
+                "SELECT name FROM sqlite_master WHERE type = 'trigger' AND tbl_name = ? ORDER BY name",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+        unrepresented.extend(triggers.iter().filter_map(|row| {
+            Some(UnrepresentedState::Table(format!(
+                "the trigger '{}'",
+                text(row, "name")?
+            )))
+        }));
+        Ok((columns, unrepresented))
+    }
+
+    async fn postgres_indexes(&self, table_name: &str) -> TikalResult<Vec<IntrospectedIndex>> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT i.relname::text AS index_name, a.attname::text AS column_name, \
+                        ix.indisunique AS is_unique, \
+                        EXISTS (SELECT 1 FROM pg_constraint con \
+                                WHERE con.conindid = ix.indexrelid) AS is_constraint \
+                 FROM pg_index ix \
+                 JOIN pg_class t ON t.oid = ix.indrelid \
+                 JOIN pg_class i ON i.oid = ix.indexrelid \
+                 JOIN pg_namespace n ON n.oid = t.relnamespace \
+                 JOIN LATERAL unnest(ix.indkey) WITH ORDINALITY AS k(attnum, ord) ON true \
+                 JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum \
+                 WHERE n.nspname = current_schema() AND t.relname = $1 AND NOT ix.indisprimary \
+                 ORDER BY i.relname, k.ord",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        Ok(group_indexes(&rows, |row| int(row, "is_constraint") != 0))
+    }
+
+    async fn mysql_indexes(&self, table_name: &str) -> TikalResult<Vec<IntrospectedIndex>> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT index_name AS index_name, column_name AS column_name, \
+                        CASE WHEN non_unique = 0 THEN 1 ELSE 0 END AS is_unique \
+                 FROM information_schema.statistics \
+                 WHERE table_schema = DATABASE() AND table_name = ? AND index_name <> 'PRIMARY' \
+                 ORDER BY index_name, seq_in_index",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        // MySQL does not distinguish UNIQUE columns from unique indexes
+        Ok(group_indexes(&rows, |_| false))
+    }
+
+    async fn sqlite_indexes(&self, table_name: &str) -> TikalResult<Vec<IntrospectedIndex>> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT il.name AS index_name, ii.name AS column_name, \
+                        il.\"unique\" AS is_unique, il.origin AS origin \
+                 FROM pragma_index_list(?) il \
+                 JOIN pragma_index_info(il.name) ii \
+                 WHERE il.origin <> 'pk' \
+                 ORDER BY il.name, ii.seqno",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        Ok(group_indexes(&rows, |row| {
+            text(row, "origin").as_deref() == Some("u")
+        }))
+    }
+
+    async fn postgres_foreign_keys(
+        &self,
+        table_name: &str,
+    ) -> TikalResult<Vec<ForeignKeyDefinition>> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT tc.constraint_name::text AS constraint_name, \
+                        kcu.column_name::text AS column_name, \
+                        rk.table_name::text AS referenced_table, \
+                        rk.column_name::text AS referenced_column, \
+                        rc.update_rule::text AS on_update, rc.delete_rule::text AS on_delete \
+                 FROM information_schema.table_constraints tc \
+                 JOIN information_schema.key_column_usage kcu \
+                   ON kcu.constraint_name = tc.constraint_name \
+                  AND kcu.constraint_schema = tc.constraint_schema \
+                 JOIN information_schema.referential_constraints rc \
+                   ON rc.constraint_name = tc.constraint_name \
+                  AND rc.constraint_schema = tc.constraint_schema \
+                 JOIN information_schema.key_column_usage rk \
+                   ON rk.constraint_name = rc.unique_constraint_name \
+                  AND rk.constraint_schema = rc.unique_constraint_schema \
+                  AND rk.ordinal_position = kcu.position_in_unique_constraint \
+                 WHERE tc.constraint_type = 'FOREIGN KEY' \
+                   AND tc.table_schema = current_schema() AND tc.table_name = $1 \
+                 ORDER BY tc.constraint_name, kcu.ordinal_position",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        Ok(group_foreign_keys(&rows, |row| {
+            text(row, "constraint_name").unwrap_or_default()
+        }))
+    }
+
+    async fn mysql_foreign_keys(&self, table_name: &str) -> TikalResult<Vec<ForeignKeyDefinition>> {
+        let rows = self
+            .executor
+            .fetch_all(
+                "SELECT kcu.constraint_name AS constraint_name, kcu.column_name AS column_name, \
+                        kcu.referenced_table_name AS referenced_table, \
+                        kcu.referenced_column_name AS referenced_column, \
+                        rc.update_rule AS on_update, rc.delete_rule AS on_delete \
+                 FROM information_schema.key_column_usage kcu \
+                 JOIN information_schema.referential_constraints rc \
+                   ON rc.constraint_schema = kcu.table_schema \
+                  AND rc.constraint_name = kcu.constraint_name \
+                  AND rc.table_name = kcu.table_name \
+                 WHERE kcu.table_schema = DATABASE() AND kcu.table_name = ? \
+                   AND kcu.referenced_table_name IS NOT NULL \
+                 ORDER BY kcu.constraint_name, kcu.ordinal_position",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        Ok(group_foreign_keys(&rows, |row| {
+            text(row, "constraint_name").unwrap_or_default()
+        }))
+    }
+
+    async fn sqlite_foreign_keys(
+        &self,
+        table_name: &str,
+    ) -> TikalResult<Vec<ForeignKeyDefinition>> {
+        let mut rows = self
+            .executor
+            .fetch_all(
+                "SELECT id, \"table\" AS referenced_table, \"from\" AS column_name, \
+                        \"to\" AS referenced_column, on_update, on_delete \
+                 FROM pragma_foreign_key_list(?) ORDER BY id, seq",
+                vec![Value::Text(table_name.to_string())],
+            )
+            .await?;
+
+        // A foreign key declared without a column list references the primary key
+        for row in rows.iter_mut() {
+            if text(row, "referenced_column").is_some() {
+                continue;
+            }
+            let referenced_table = text(row, "referenced_table").unwrap_or_default();
+            let primary_key = self
+                .sqlite_columns(&referenced_table)
+                .await?
+                .0
+                .into_iter()
+                .find(|c| c.primary_key)
+                .map(|c| Value::Text(c.name))
+                .unwrap_or(Value::Null);
+            row.insert("referenced_column".to_string(), primary_key);
+        }
+
+        let mut foreign_keys = group_foreign_keys(&rows, |row| int(row, "id").to_string());
+        for foreign_key in foreign_keys.iter_mut() {
+            foreign_key.name = format!("fk_{}_{}", table_name, foreign_key.columns.join("_"));
+        }
+        Ok(foreign_keys)
+    }
+}
+
+fn group_indexes(rows: &[Row], is_constraint: impl Fn(&Row) -> bool) -> Vec<IntrospectedIndex> {
+    let mut indexes: Vec<IntrospectedIndex> = Vec::new();
+    for row in rows {
+        let (Some(name), Some(column)) = (text(row, "index_name"), text(row, "column_name")) else {
+            continue;
+        };
+        match indexes.last_mut() {
+            Some(last) if last.index.name == name => last.index.columns.push(column),
+            _ => indexes.push(IntrospectedIndex {
+                index: IndexDefinition {
+                    name,
+                    columns: vec![column],
+                    unique: int(row, "is_unique") != 0,
+                },
+                constraint: is_constraint(row),
+            }),
+        }
+    }
+    indexes
+}
+
+fn group_foreign_keys(rows: &[Row], key: impl Fn(&Row) -> String) -> Vec<ForeignKeyDefinition> {
+    let mut foreign_keys: Vec<(String, ForeignKeyDefinition)> = Vec::new();
+    for row in rows {
+        let (Some(column), Some(referenced_column)) =
+            (text(row, "column_name"), text(row, "referenced_column"))
+        else {
+            continue;
+        };
+        let row_key = key(row);
+        match foreign_keys.last_mut() {
+            Some((last_key, foreign_key)) if *last_key == row_key => {
+                foreign_key.columns.push(column);
+                foreign_key.referenced_columns.push(referenced_column);
+            }
+            _ => foreign_keys.push((
+                row_key.clone(),
+                ForeignKeyDefinition {
+                    name: row_key,
+                    columns: vec![column],
+                    referenced_table: text(row, "referenced_table").unwrap_or_default(),
+                    referenced_columns: vec![referenced_column],
+                    on_delete: ReferentialAction::from_sql(
+                        &text(row, "on_delete").unwrap_or_default(),
+                    ),
+                    on_update: ReferentialAction::from_sql(
+                        &text(row, "on_update").unwrap_or_default(),
+                    ),
+                },
+            )),
+        }
+    }
+    foreign_keys.into_iter().map(|(_, fk)| fk).collect()
+}
+
+fn postgres_type(udt_name: &str, enums: &HashMap<String, Vec<String>>) -> ColumnType {
+    match enums.get(udt_name) {
+        Some(variants) => ColumnType::Enum {
+            name: udt_name.to_string(),
+            variants: variants.clone(),
+        },
+        None => column_type_from_sql(udt_name),
+    }
+}
+
+// MySQL reports enums as enum('a','b'), with quotes inside labels doubled
+fn mysql_enum_variants(sql_type: &str) -> Option<Vec<String>> {
+    let list = sql_type
+        .strip_prefix("enum(")
+        .or_else(|| sql_type.strip_prefix("ENUM("))?
+        .strip_suffix(')')?;
+
+    let mut variants = Vec::new();
+    let mut chars = list.chars().peekable();
+    while chars.next() == Some('\'') {
+        let mut variant = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() != Some(&'\'') {
+                    break;
+                }
+                chars.next();
+            }
+            variant.push(c);
+        }
+        variants.push(variant);
+        if chars.next() != Some(',') {
+            break;
+        }
+    }
+    Some(variants)
+}
+
+// Table state that SQLite keeps only in the CREATE TABLE text
+static SQLITE_TABLE_STATE: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    [
+        (r"(?i)\bCHECK\s*\(", "CHECK constraints"),
+        (r"(?i)\bCOLLATE\b", "collations"),
+        (r"(?i)\bGENERATED\s+ALWAYS\b|\bAS\s*\(", "generated columns"),
+        (r"(?i)\bWITHOUT\s+ROWID\b", "the WITHOUT ROWID option"),
+        (r"(?i)\)\s*STRICT\b", "the STRICT option"),
+    ]
+    .into_iter()
+    .map(|(pattern, description)| (Regex::new(pattern).unwrap(), description))
+    .collect()
+});
+
+// The variants of a check written as CHECK ("column" IN ('a', 'b')), along with
+// the span of the check
+fn sqlite_enum_check(
+    table_sql: &str,
+    check: &str,
+) -> Option<(Vec<String>, std::ops::Range<usize>)> {
+    let start = table_sql.find(check)?;
+    let mut rest = &table_sql[start + check.len()..];
+    let mut variants = Vec::new();
+    loop {
+        let mut variant = String::new();
+        let mut chars = rest.strip_prefix('\'')?.char_indices().peekable();
+        let end = loop {
+            let (i, c) = chars.next()?;
+            if c == '\'' {
+                if chars.peek().map(|(_, c)| *c) != Some('\'') {
+                    break i + 2;
+                }
+                chars.next();
+            }
+            variant.push(c);
+        };
+        variants.push(variant);
+        rest = &rest[end..];
+        if let Some(next) = rest.strip_prefix(", ") {
+            rest = next;
+        } else {
+            let rest = rest.strip_prefix("))")?;
+            return Some((variants, start..table_sql.len() - rest.len()));
+        }
+    }
+}
+
+// MySQL reports INT where Tikal writes INTEGER, and older servers add display widths
+fn mysql_type(sql_type: &str) -> String {
+    static DISPLAY_WIDTH: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^(tinyint|smallint|mediumint|int|bigint)\(\d+\)").unwrap());
+    let sql_type: String = sql_type
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let sql_type = match sql_type.strip_prefix("integer") {
+        Some(rest) => format!("int{}", rest),
+        None => sql_type,
+    };
+    if sql_type.starts_with("tinyint(1)") {
+        return sql_type;
+    }
+    DISPLAY_WIDTH.replace(&sql_type, "$1").into_owned()
+}
+
+pub fn column_type_from_sql(sql_type: &str) -> ColumnType {
+    let sql_type = sql_type.to_lowercase();
+    let base = sql_type.split('(').next().unwrap_or_default().trim();
+
+    match base {
+        "bigint" | "int8" | "bigserial" => ColumnType::BigInt,
+        "boolean" | "bool" => ColumnType::Bool,
+        "tinyint" if sql_type.starts_with("tinyint(1)") => ColumnType::Bool,
+        "real" | "double" | "double precision" | "float" | "float4" | "float8" => ColumnType::Float,
+        "numeric" | "decimal" => ColumnType::Decimal,
+        "uuid" => ColumnType::Uuid,
+        // MySQL has no UUID type, so CHAR(36) is how Tikal stores one
+        "char" if sql_type.starts_with("char(36)") => ColumnType::Uuid,
+        "date" => ColumnType::Date,
+        "time" | "time without time zone" => ColumnType::Time,
+        _ if base.starts_with("interval") => ColumnType::Interval,
+        "timestamp with time zone" | "timestamptz" | "datetime" => ColumnType::DateTime,
+        "timestamp" | "timestamp without time zone" => ColumnType::NaiveDateTime,
+        "json" | "jsonb" => ColumnType::Json,
+        "blob" | "longblob" | "mediumblob" | "tinyblob" | "bytea" | "binary" | "varbinary" => {
+            ColumnType::Binary
+        }
+        "longtext" | "mediumtext" => ColumnType::LongText,
+        _ if base.contains("int") || base.contains("serial") => ColumnType::Int,
+        _ => ColumnType::Text,
+    }
+}
+
+// Only literal defaults are kept; expressions such as CURRENT_TIMESTAMP or
+// nextval(...) have no Value representation.
+pub(crate) fn parse_default(expr: &str, column_type: &ColumnType) -> Option<Value> {
+    let expr = expr.trim();
+    let expr = expr
+        .strip_prefix('(')
+        .and_then(|e| e.strip_suffix(')'))
+        .unwrap_or(expr);
+
+    if let Some(rest) = expr.strip_prefix('\'') {
+        let mut literal = String::new();
+        let mut chars = rest.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() != Some(&'\'') {
+                    // Anything after the closing quote is a cast such as ::text
+                    return Some(literal_value(literal, column_type));
+                }
+                chars.next();
+            }
+            literal.push(c);
+        }
+        return None;
+    }
+
+    let is_literal = expr.parse::<f64>().is_ok()
+        || expr.eq_ignore_ascii_case("true")
+        || expr.eq_ignore_ascii_case("false");
+    is_literal.then(|| literal_value(expr.to_string(), column_type))
+}
+
+fn literal_value(literal: String, column_type: &ColumnType) -> Value {
+    match column_type {
+        ColumnType::Int | ColumnType::BigInt | ColumnType::Id => literal
+            .parse()
+            .map(Value::Int)
+            .unwrap_or(Value::Text(literal)),
+        ColumnType::Float => literal
+            .parse::<f64>()
+            .map(|f| Value::Float(f.into()))
+            .unwrap_or(Value::Text(literal)),
+        ColumnType::Decimal => literal
+            .parse()
+            .map(Value::Decimal)
+            .unwrap_or(Value::Text(literal)),
+        ColumnType::Bool => match literal.to_lowercase().as_str() {
+            "1" | "true" | "t" => Value::Bool(true),
+            "0" | "false" | "f" => Value::Bool(false),
+            _ => Value::Text(literal),
+        },
+        _ => Value::Text(literal),
+    }
+}
+
+fn text(row: &Row, key: &str) -> Option<String> {
+    match row.get(key)? {
+        Value::Text(s) => Some(s.clone()),
+        Value::Binary(b) => String::from_utf8(b.clone()).ok(),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+fn int(row: &Row, key: &str) -> i64 {
+    match row.get(key) {
+        Some(Value::Int(i)) => *i,
+        Some(Value::Bool(b)) => *b as i64,
+        Some(Value::Text(s)) => s.parse().unwrap_or_default(),
+        _ => 0,
+    }
+}
@@ -1,7 +1,14 @@
+pub mod alter;
 pub mod builders;
+pub mod codegen;
+pub mod diff;
 pub mod generators;
+pub mod introspector;
 pub mod types;
 
+pub use alter::alter_table;
 pub use builders::*;
+pub use diff::{in_dependency_order, SchemaChange, SchemaDiff, SchemaDiffer};
 pub use generators::{DdlGenerator, UnifiedDdlGenerator};
+pub use introspector::SchemaIntrospector;
 pub use types::*;
@@ -1,3 +1,4 @@
+use crate::domain::model::relationships::{RelationshipMap, RelationshipMeta, RelationshipType};
 use crate::domain::value_objects::Value;
 
 #[derive(Debug, Clone)]
@@ -10,6 +11,9 @@ pub struct TableDefinition {
     pub name: String,
     pub columns: Vec<ColumnDefinition>,
     pub indexes: Vec<IndexDefinition>,
+    pub foreign_keys: Vec<ForeignKeyDefinition>,
+    // Filled by introspection, see UnrepresentedState
+    pub unrepresented: Vec<UnrepresentedState>,
 }
 
 #[derive(Debug, Clone)]
@@ -36,6 +40,57 @@ pub enum ColumnType {
     NaiveDateTime,
     Json,
     Binary,
+    Decimal,
+    Uuid,
+    Date,
+    Time,
+    Interval,
+    Array(Box<ColumnType>),
+    // A named enum type on PostgreSQL, ENUM(...) on MySQL and a CHECK on SQLite
+    Enum { name: String, variants: Vec<String> },
+}
+
+// State introspection found in the database that the definition cannot
+// express. Alterations that would restate it refuse to run instead of dropping it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnrepresentedState {
+    // A type with no exact column type, such as VARCHAR(100)
+    Type { column: String, sql_type: String },
+    // A default that is an expression rather than a literal
+    Default { column: String, expression: String },
+    // Other column state, such as a collation or comment
+    Column { column: String, description: String },
+    // Table state such as CHECK constraints and triggers
+    Table(String),
+}
+
+impl UnrepresentedState {
+    pub fn column(&self) -> Option<&str> {
+        match self {
+            UnrepresentedState::Type { column, .. }
+            | UnrepresentedState::Default { column, .. }
+            | UnrepresentedState::Column { column, .. } => Some(column),
+            UnrepresentedState::Table(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for UnrepresentedState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnrepresentedState::Type { column, sql_type } => {
+                write!(f, "the type {} of column '{}'", sql_type, column)
+            }
+            UnrepresentedState::Default { column, expression } => {
+                write!(f, "the default {} of column '{}'", expression, column)
+            }
+            UnrepresentedState::Column {
+                column,
+                description,
+            } => write!(f, "the {} of column '{}'", description, column),
+            UnrepresentedState::Table(description) => write!(f, "{}", description),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -44,3 +99,108 @@ pub struct IndexDefinition {
     pub columns: Vec<String>,
     pub unique: bool,
 }
+
+#[derive(Debug, Clone)]
+pub struct AlterTableDefinition {
+    pub table: String,
+    pub operations: Vec<AlterOperation>,
+}
+
+#[derive(Debug, Clone)]
+pub enum AlterOperation {
+    AddColumn(ColumnDefinition),
+    DropColumn(String),
+    RenameColumn {
+        from: String,
+        to: String,
+    },
+    ChangeType {
+        column: String,
+        column_type: ColumnType,
+    },
+    SetNullable {
+        column: String,
+        nullable: bool,
+    },
+    SetDefault {
+        column: String,
+        default_value: Option<Value>,
+    },
+    AddIndex(IndexDefinition),
+    DropIndex(String),
+    AddForeignKey(ForeignKeyDefinition),
+    DropForeignKey(String),
+    RenameTable(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ForeignKeyDefinition {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+    pub on_delete: ReferentialAction,
+    pub on_update: ReferentialAction,
+}
+
+impl ForeignKeyDefinition {
+    pub fn from_relationship(table: &str, relationship: &RelationshipMeta) -> Option<Self> {
+        if relationship.rel_type != RelationshipType::BelongsTo {
+            return None;
+        }
+        Some(Self {
+            name: format!("fk_{}_{}", table, relationship.foreign_key),
+            columns: vec![relationship.foreign_key.clone()],
+            referenced_table: relationship.target_table.clone(),
+            // Relationship joins always target the id column
+            referenced_columns: vec!["id".to_string()],
+            on_delete: if relationship.cascade_delete {
+                ReferentialAction::Cascade
+            } else {
+                ReferentialAction::NoAction
+            },
+            on_update: ReferentialAction::NoAction,
+        })
+    }
+
+    pub fn from_relationships(table: &str, relationships: &RelationshipMap) -> Vec<Self> {
+        let mut foreign_keys: Vec<Self> = relationships
+            .values()
+            .filter_map(|relationship| Self::from_relationship(table, relationship))
+            .collect();
+        foreign_keys.sort_by(|a, b| a.name.cmp(&b.name));
+        foreign_keys
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReferentialAction {
+    #[default]
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+impl ReferentialAction {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        }
+    }
+
+    pub fn from_sql(action: &str) -> Self {
+        match action.trim().to_uppercase().as_str() {
+            "RESTRICT" => ReferentialAction::Restrict,
+            "CASCADE" => ReferentialAction::Cascade,
+            "SET NULL" => ReferentialAction::SetNull,
+            "SET DEFAULT" => ReferentialAction::SetDefault,
+            _ => ReferentialAction::NoAction,
+        }
+    }
+}
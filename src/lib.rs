@@ -18,9 +18,11 @@ pub async fn init() -> Result<application::TikalApp, Box<dyn std::error::Error>>
 }
 
 pub mod prelude {
-    pub use crate::application::TikalApp;
+    pub use crate::application::{Factory, Seeder, SeederRunner, TikalApp, TikalTransaction};
     pub use crate::domain::model::{ActiveModel, Entity, FromRow, Lazy, ModelMapping, Validate};
+    pub use crate::domain::query::assignment::Assignment;
     pub use crate::domain::query::builder::{Operator, OrderDirection, QueryBuilder};
+    pub use crate::domain::repositories::types::{IsolationLevel, TransactionOptions};
     pub use crate::domain::repositories::Repository;
     pub use crate::domain::value_objects::{FromValue, Value};
     pub use crate::domain::{TikalError, TikalResult};
@@ -0,0 +1,6 @@
+// Used by both tikal and tikal_macros, which cannot depend on each other
+mod migration_file;
+mod type_mapping;
+
+pub use migration_file::parse_file_name;
+pub use type_mapping::{TYPE_MAPPINGS, TypeMapping};
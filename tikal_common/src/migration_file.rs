@@ -0,0 +1,13 @@
+pub fn parse_file_name(file_name: &str) -> Option<(u64, String, bool)> {
+    let (stem, is_up) = if let Some(stem) = file_name.strip_suffix(".up.sql") {
+        (stem, true)
+    } else {
+        (file_name.strip_suffix(".down.sql")?, false)
+    };
+    let (version, name) = stem.split_once('_')?;
+    let version = version.parse().ok()?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((version, name.to_string(), is_up))
+}
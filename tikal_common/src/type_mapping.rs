@@ -0,0 +1,91 @@
+// Rust types a field can use and the ColumnType variant each maps to. The
+// derive matches on the last path segment, schema codegen writes the full path
+// of the first entry for a variant.
+pub struct TypeMapping {
+    pub type_name: &'static str,
+    pub rust_type: &'static str,
+    pub column_type: &'static str,
+}
+
+pub const TYPE_MAPPINGS: &[TypeMapping] = &[
+    TypeMapping {
+        type_name: "String",
+        rust_type: "String",
+        column_type: "Text",
+    },
+    TypeMapping {
+        type_name: "i64",
+        rust_type: "i64",
+        column_type: "BigInt",
+    },
+    TypeMapping {
+        type_name: "i32",
+        rust_type: "i32",
+        column_type: "Int",
+    },
+    TypeMapping {
+        type_name: "i16",
+        rust_type: "i16",
+        column_type: "Int",
+    },
+    TypeMapping {
+        type_name: "i8",
+        rust_type: "i8",
+        column_type: "Int",
+    },
+    TypeMapping {
+        type_name: "f64",
+        rust_type: "f64",
+        column_type: "Float",
+    },
+    TypeMapping {
+        type_name: "f32",
+        rust_type: "f32",
+        column_type: "Float",
+    },
+    TypeMapping {
+        type_name: "bool",
+        rust_type: "bool",
+        column_type: "Bool",
+    },
+    TypeMapping {
+        type_name: "DateTime",
+        rust_type: "chrono::DateTime<chrono::Utc>",
+        column_type: "DateTime",
+    },
+    TypeMapping {
+        type_name: "NaiveDateTime",
+        rust_type: "chrono::NaiveDateTime",
+        column_type: "NaiveDateTime",
+    },
+    TypeMapping {
+        type_name: "Value",
+        rust_type: "serde_json::Value",
+        column_type: "Json",
+    },
+    TypeMapping {
+        type_name: "Decimal",
+        rust_type: "rust_decimal::Decimal",
+        column_type: "Decimal",
+    },
+    TypeMapping {
+        type_name: "Uuid",
+        rust_type: "uuid::Uuid",
+        column_type: "Uuid",
+    },
+    TypeMapping {
+        type_name: "NaiveDate",
+        rust_type: "chrono::NaiveDate",
+        column_type: "Date",
+    },
+    TypeMapping {
+        type_name: "NaiveTime",
+        rust_type: "chrono::NaiveTime",
+        column_type: "Time",
+    },
+    TypeMapping {
+        type_name: "Interval",
+        rust_type: "tikal::domain::value_objects::Interval",
+        column_type: "Interval",
+    },
+];
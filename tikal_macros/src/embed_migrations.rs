@@ -0,0 +1,79 @@
+use proc_macro_error::abort;
+use quote::quote;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use syn::LitStr;
+use tikal_common::parse_file_name;
+
+pub fn expand_embed_migrations(input: LitStr) -> proc_macro2::TokenStream {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let dir = PathBuf::from(manifest_dir).join(input.value());
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) => abort!(
+            input,
+            "Cannot read migrations directory '{}': {}",
+            dir.display(),
+            e
+        ),
+    };
+
+    let mut scripts: BTreeMap<(u64, String), (Option<String>, Option<String>)> = BTreeMap::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        if !file_name.ends_with(".sql") {
+            continue;
+        }
+        let Some((version, name, is_up)) = parse_file_name(file_name) else {
+            abort!(
+                input,
+                "Invalid migration file name '{}', expected <version>_<name>.up.sql or .down.sql",
+                file_name
+            );
+        };
+
+        let path = path.to_string_lossy().into_owned();
+        let slot = scripts.entry((version, name)).or_default();
+        if is_up {
+            slot.0 = Some(path);
+        } else {
+            slot.1 = Some(path);
+        }
+    }
+
+    let migrations = scripts.into_iter().map(|((version, name), (up, down))| {
+        let Some(up) = up else {
+            abort!(
+                input,
+                "Migration '{}_{}' has a down script but no up script",
+                version,
+                name
+            );
+        };
+        let down = match down {
+            Some(down) => quote! { Some(include_str!(#down).to_string()) },
+            None => quote! { None },
+        };
+
+        quote! {
+            Box::new(tikal::infrastructure::migrations::sql::SqlMigration::new(
+                #version,
+                #name,
+                include_str!(#up),
+                #down,
+            )) as Box<dyn tikal::infrastructure::migrations::Migration>
+        }
+    });
+
+    quote! {
+        {
+            let migrations: Vec<Box<dyn tikal::infrastructure::migrations::Migration>> =
+                vec![#(#migrations),*];
+            migrations
+        }
+    }
+}
@@ -2,29 +2,34 @@ use heck::ToSnakeCase;
 use proc_macro_error::abort;
 use quote::quote;
 use syn::{Data, DataStruct, DeriveInput, Fields, Type};
-use crate::utils::{extract_table_name, extract_primary_key, extract_column_name, extract_option_inner_type, get_type_mapping};
+use crate::utils::{extract_table_name, extract_primary_key, extract_column_name, extract_option_inner_type, extract_vec_inner_type, get_type_mapping, extract_belongs_to, has_cascade_delete, extract_enum_type};
 use crate::type_conversion::generate_to_value;
 
-fn map_type_to_column_type(ty: &Type) -> proc_macro2::TokenStream {
+fn map_type_to_column_type(ty: &Type, enum_type: Option<&(String, Vec<String>)>) -> proc_macro2::TokenStream {
     if let Some(inner) = extract_option_inner_type(ty) {
-        return map_type_to_column_type(&inner);
+        return map_type_to_column_type(&inner, enum_type);
+    }
+
+    if let Some(inner) = extract_vec_inner_type(ty) {
+        if matches!(&inner, Type::Path(p) if p.path.is_ident("u8")) {
+            return quote! { tikal::infrastructure::schema::types::ColumnType::Binary };
+        }
+        let inner = map_type_to_column_type(&inner, enum_type);
+        return quote! { tikal::infrastructure::schema::types::ColumnType::Array(Box::new(#inner)) };
+    }
+
+    if let Some((name, variants)) = enum_type {
+        return quote! {
+            tikal::infrastructure::schema::types::ColumnType::Enum {
+                name: #name.to_string(),
+                variants: vec![#(#variants.to_string()),*],
+            }
+        };
     }
 
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
             let type_name = segment.ident.to_string();
-            if type_name == "Vec" {
-                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                    if let Some(syn::GenericArgument::Type(Type::Path(inner_path))) = args.args.first() {
-                        if let Some(inner_segment) = inner_path.path.segments.last() {
-                            if inner_segment.ident == "u8" {
-                                return quote! { tikal::infrastructure::schema::types::ColumnType::Binary };
-                            }
-                        }
-                    }
-                }
-                return quote! { tikal::infrastructure::schema::types::ColumnType::Text };
-            }
             let mapping = get_type_mapping(&type_name);
             let column_type_ident = syn::Ident::new(mapping.column_type, proc_macro2::Span::call_site());
             return quote! { tikal::infrastructure::schema::types::ColumnType::#column_type_ident };
@@ -45,6 +50,7 @@ pub fn expand_entity_derive(input: DeriveInput) -> proc_macro2::TokenStream {
 
     let mut insertions = Vec::new();
     let mut column_defs = Vec::new();
+    let mut relationships = Vec::new();
 
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
@@ -53,11 +59,32 @@ pub fn expand_entity_derive(input: DeriveInput) -> proc_macro2::TokenStream {
 
         let value_expr = generate_to_value(field_name, &field.ty);
 
+        if let Some(target_table) = extract_belongs_to(field) {
+            let relationship_name = column_name
+                .strip_suffix("_id")
+                .unwrap_or(&column_name)
+                .to_string();
+            let cascade_delete = if has_cascade_delete(field) {
+                quote! { .cascade_delete() }
+            } else {
+                quote! {}
+            };
+            relationships.push(quote! {
+                tikal::domain::model::relationships::RelationshipMeta::belongs_to(
+                    #relationship_name.to_string(),
+                    #column_name.to_string(),
+                    #target_table.to_string(),
+                )#cascade_delete
+            });
+        } else if has_cascade_delete(field) {
+            abort!(field, "#[cascade_delete] requires #[belongs_to = \"table\"] on the same field");
+        }
+
         insertions.push(quote! {
             values.insert(#column_name.to_string(), #value_expr);
         });
 
-        let column_type = map_type_to_column_type(&field.ty);
+        let column_type = map_type_to_column_type(&field.ty, extract_enum_type(field).as_ref());
         let nullable = extract_option_inner_type(&field.ty).is_some();
         let primary_key = field_name.to_string() == primary_key;
         let auto_increment = primary_key && matches!(field.ty, Type::Path(ref p) if p.path.segments.last().map(|s| s.ident == "i64").unwrap_or(false));
@@ -75,6 +102,19 @@ pub fn expand_entity_derive(input: DeriveInput) -> proc_macro2::TokenStream {
         });
     }
 
+    // Without #[belongs_to] fields the trait's default (no relationships) applies
+    let relationships_fn = if relationships.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn relationships() -> tikal::domain::model::relationships::RelationshipMap {
+                tikal::domain::model::relationships::utils::build_relationship_map(vec![
+                    #(#relationships),*
+                ])
+            }
+        }
+    };
+
     quote! {
         impl tikal::domain::model::Entity for #struct_name {
             fn table_name() -> &'static str {
@@ -91,11 +131,18 @@ pub fn expand_entity_derive(input: DeriveInput) -> proc_macro2::TokenStream {
                 values
             }
 
+            #relationships_fn
+
             fn table_definition() -> tikal::infrastructure::schema::types::TableDefinition {
                 tikal::infrastructure::schema::types::TableDefinition {
                     name: #table_name.to_string(),
                     columns: vec![#(#column_defs),*],
                     indexes: vec![],
+                    foreign_keys: tikal::infrastructure::schema::types::ForeignKeyDefinition::from_relationships(
+                        #table_name,
+                        &Self::relationships(),
+                    ),
+                    unrepresented: vec![],
                 }
             }
 
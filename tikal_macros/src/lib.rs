@@ -1,14 +1,18 @@
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, LitStr};
 
+mod embed_migrations;
 mod entity;
 mod from_row;
 mod model_mapping;
 mod utils;
 mod type_conversion;
 
-#[proc_macro_derive(Entity, attributes(table_name, primary_key, column_name))]
+#[proc_macro_derive(
+    Entity,
+    attributes(table_name, primary_key, column_name, belongs_to, cascade_delete, enum_type)
+)]
 #[proc_macro_error]
 pub fn derive_entity(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -27,4 +31,11 @@ pub fn derive_from_row(input: TokenStream) -> TokenStream {
 pub fn derive_model_mapping(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     model_mapping::expand_model_mapping_derive(input).into()
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as LitStr);
+    embed_migrations::expand_embed_migrations(input).into()
 }
\ No newline at end of file
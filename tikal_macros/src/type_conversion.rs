@@ -82,6 +82,13 @@ fn generate_to_value_for_type(
                         quote! { tikal::domain::value_objects::Value::NaiveDateTime(self.#field_name) }
                     }
                 },
+                "Decimal" | "Uuid" | "NaiveDate" | "NaiveTime" | "Interval" => {
+                    if is_option_inner {
+                        quote! { tikal::domain::value_objects::Value::from(*val) }
+                    } else {
+                        quote! { tikal::domain::value_objects::Value::from(self.#field_name) }
+                    }
+                },
                 "Value" if type_path.path.segments.iter().any(|s| s.ident == "serde_json") => {
                     if is_option_inner {
                         quote! { tikal::domain::value_objects::Value::Json(val.clone()) }
@@ -96,8 +103,18 @@ fn generate_to_value_for_type(
                         } else {
                             quote! { tikal::domain::value_objects::Value::Binary(self.#field_name.clone()) }
                         }
+                    } else if is_option_inner {
+                        quote! {
+                            tikal::domain::value_objects::Value::Array(
+                                val.iter().cloned().map(tikal::domain::value_objects::Value::from).collect()
+                            )
+                        }
                     } else {
-                        fallback_conversion(field_name, is_option_inner)
+                        quote! {
+                            tikal::domain::value_objects::Value::Array(
+                                self.#field_name.iter().cloned().map(tikal::domain::value_objects::Value::from).collect()
+                            )
+                        }
                     }
                 },
                 _ => fallback_conversion(field_name, is_option_inner)
@@ -241,6 +258,13 @@ fn generate_from_value_inner(
                         )),
                     }
                 },
+                // FromValue also parses the text these are stored as on some drivers
+                "Decimal" | "Uuid" | "NaiveDate" | "NaiveTime" | "Interval" | "Vec" => quote! {
+                    <#field_type as tikal::domain::value_objects::FromValue>::from_value(#get_value)
+                        .map_err(|e| tikal::domain::TikalError::db(
+                            &format!("{} for column '{}'", e, #column_name)
+                        ))?
+                },
                 _ => quote! {
                     return Err(tikal::domain::TikalError::db(
                         &format!("Unsupported field type for column '{}'", #column_name)
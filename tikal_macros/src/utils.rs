@@ -1,5 +1,8 @@
 use heck::ToSnakeCase;
-use syn::{Field, Lit, DeriveInput};
+use proc_macro_error::abort;
+use syn::punctuated::Punctuated;
+use syn::{Field, Lit, LitStr, DeriveInput, Token};
+use tikal_common::{TypeMapping, TYPE_MAPPINGS};
 
 pub fn extract_column_name(field: &Field) -> Option<String> {
     for attr in &field.attrs {
@@ -16,6 +19,43 @@ pub fn extract_column_name(field: &Field) -> Option<String> {
     None
 }
 
+pub fn extract_belongs_to(field: &Field) -> Option<String> {
+    for attr in &field.attrs {
+        if attr.path().is_ident("belongs_to")
+            && let Ok(meta) = attr.meta.require_name_value()
+            && let syn::Expr::Lit(lit) = &meta.value
+            && let Lit::Str(lit_str) = &lit.lit
+        {
+            return Some(lit_str.value());
+        }
+    }
+    None
+}
+
+// #[enum_type("mood", "happy", "sad")]: the type name, then its variants
+pub fn extract_enum_type(field: &Field) -> Option<(String, Vec<String>)> {
+    for attr in &field.attrs {
+        if attr.path().is_ident("enum_type") {
+            let args = attr
+                .parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)
+                .unwrap_or_else(|e| abort!(attr, "{}", e));
+            let mut args = args.iter().map(|lit| lit.value());
+            let Some(name) = args.next() else {
+                abort!(attr, "#[enum_type] expects the type name followed by its variants");
+            };
+            return Some((name, args.collect()));
+        }
+    }
+    None
+}
+
+pub fn has_cascade_delete(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("cascade_delete"))
+}
+
 pub fn extract_table_name(input: &DeriveInput) -> String {
     for attr in &input.attrs {
         if attr.path().is_ident("table_name") {
@@ -63,6 +103,18 @@ pub fn extract_option_inner_type(option_type: &syn::Type) -> Option<syn::Type> {
     None
 }
 
+pub fn extract_vec_inner_type(vec_type: &syn::Type) -> Option<syn::Type> {
+    if let syn::Type::Path(type_path) = vec_type
+        && let Some(segment) = type_path.path.segments.last()
+        && segment.ident == "Vec"
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        return Some(inner.clone());
+    }
+    None
+}
+
 pub fn pluralize(word: &str) -> String {
     if word.is_empty() {
         return word.to_string();
@@ -83,40 +135,11 @@ pub fn pluralize(word: &str) -> String {
     }
 }
 
-#[derive(Debug)]
-pub struct TypeMapping {
-    pub column_type: &'static str,
-}
-
-pub fn get_type_mapping(type_name: &str) -> TypeMapping {
-    match type_name {
-        "String" => TypeMapping {
-            column_type: "Text",
-        },
-        "i64" => TypeMapping {
-            column_type: "BigInt",
-        },
-        "i32" | "i16" | "i8" => TypeMapping {
-            column_type: "Int",
-        },
-        "f64" | "f32" => TypeMapping {
-            column_type: "Float",
-        },
-        "bool" => TypeMapping {
-            column_type: "Bool",
-        },
-        "DateTime" => TypeMapping {
-            column_type: "DateTime",
-        },
-        "NaiveDateTime" => TypeMapping {
-            column_type: "NaiveDateTime",
-        },
-        "Value" => TypeMapping {
-            column_type: "Json",
-        },
-        _ => TypeMapping {
-            column_type: "Text",
-        },
-    }
+// Unknown types are stored as text
+pub fn get_type_mapping(type_name: &str) -> &'static TypeMapping {
+    TYPE_MAPPINGS
+        .iter()
+        .find(|mapping| mapping.type_name == type_name)
+        .unwrap_or(&TYPE_MAPPINGS[0])
 }
 